@@ -2,4 +2,8 @@
 import type { Border } from "./Border";
 import type { Position } from "./Position";
 
-export type BoardError = { dangling_borders: Array<Border>, incorrect_galaxy_sizes: Array<Position>, centerless_cells: Array<Position>, cut_centers: Array<Position>, asymmetric_centers: Array<Position>, };
+export type BoardError = { dangling_borders: Array<Border>, incorrect_galaxy_sizes: Array<Position>, centerless_cells: Array<Position>, cut_centers: Array<Position>, asymmetric_centers: Array<Position>, 
+/**
+ * Cells painted into a center that isn't in their galaxy
+ */
+misassigned_cells: Array<Position>, };
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Difficulty } from "./Difficulty";
+import type { Stats } from "./Stats";
+
+/**
+ * How a puzzle was solved, produced once when it's solved
+ */
+export type CompletionSummary = { width: number, height: number, difficulty: Difficulty, 
+/**
+ * The active solving time, in milliseconds
+ */
+elapsed_ms: number, hints_used: number, stats: Stats, };
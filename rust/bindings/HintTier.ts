@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How much a hint gives away, from least to most
+ */
+export type HintTier = "LocateMistake" | "RevealWall" | "RevealGalaxy";
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { HintTier } from "./HintTier";
+
+/**
+ * A hint tier as the UI shows it
+ */
+export type HintTierView = { tier: HintTier, cost: number, 
+/**
+ * The number of hints of this tier taken, including undone ones
+ */
+used: number, };
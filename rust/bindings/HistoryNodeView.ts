@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type HistoryNodeView = { id: number, parent: number | null, 
+/**
+ * Oldest first
+ */
+children: Array<number>, 
+/**
+ * The number of moves from the start
+ */
+depth: number, label: string, 
+/**
+ * Whether undo and redo can reach the node without jumping
+ */
+on_current_line: boolean, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What happens to undone moves when a new move is made
+ */
+export type HistoryPolicy = "tree" | "linear";
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { HistoryNodeView } from "./HistoryNodeView";
+import type { HistoryPolicy } from "./HistoryPolicy";
+
+/**
+ * The history tree as the UI renders it
+ */
+export type HistoryView = { 
+/**
+ * Every node, parents before their children, where the index is the id
+ */
+nodes: Array<HistoryNodeView>, current: number, policy: HistoryPolicy, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { InputErrorCode } from "./InputErrorCode";
+
+/**
+ * Invalid arguments passed to a wasm entry point. It is thrown to JS as an `Error`
+ * whose message is this error as JSON, so that the code can be read back.
+ */
+export type InputError = { code: InputErrorCode, message: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What was wrong with the arguments passed from JS
+ */
+export type InputErrorCode = "CellOutsideBoard" | "CellsNotAdjacent" | "UnknownCenter" | "UnknownHistoryNode" | "InvalidSize" | "MalformedValue" | "InvalidSavedGame" | "RefusedMove";
@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Border } from "./Border";
+
+/**
+ * How the walls on an unfinished board compare with the solution
+ */
+export type ProgressReport = { 
+/**
+ * Walls on the board that aren't in the solution, in order
+ */
+wrong_walls: Array<Border>, 
+/**
+ * The number of walls of the solution that are neither on the board nor given,
+ * if it was asked for
+ */
+missing_walls: number | null, };
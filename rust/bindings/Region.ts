@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Position } from "./Position";
+
+/**
+ * A region of the board enclosed by walls
+ */
+export type Region = { 
+/**
+ * The center of the objective in the region, if there is exactly one
+ */
+center: Position | null, 
+/**
+ * Whether the region is a finished galaxy: symmetric around its only center, of the
+ * right size and without walls inside. Otherwise it's still in progress.
+ */
+complete: boolean, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Region } from "./Region";
+
+/**
+ * The regions of the board, for filling finished galaxies
+ */
+export type RegionMap = { 
+/**
+ * The index of the region of each cell in [RegionMap::regions], by row
+ */
+ids: Array<Array<number>>, regions: Array<Region>, };
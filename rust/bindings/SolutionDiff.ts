@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Border } from "./Border";
+
+/**
+ * The walls on the board against the walls of the solution, each in order, so that the
+ * UI can animate the changes from one to the other
+ */
+export type SolutionDiff = { 
+/**
+ * Walls of the solution that are neither on the board nor given
+ */
+walls_to_add: Array<Border>, 
+/**
+ * Walls on the board that aren't in the solution
+ */
+walls_to_remove: Array<Border>, 
+/**
+ * Walls on the board that are in the solution
+ */
+correct_walls: Array<Border>, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Border } from "./Border";
+
+/**
+ * Whether the walls on a board can still be completed to a solution
+ */
+export type Solvability = "Solvable" | { "Unsolvable": { conflicting_walls: Array<Border>, } } | "Unknown";
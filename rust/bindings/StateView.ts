@@ -1,8 +1,62 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { BoardError } from "./BoardError";
+import type { CompletionSummary } from "./CompletionSummary";
+import type { HintTierView } from "./HintTierView";
 import type { Objective } from "./Objective";
+import type { Position } from "./Position";
+import type { RegionMap } from "./RegionMap";
+import type { SolutionDiff } from "./SolutionDiff";
+import type { Stats } from "./Stats";
 
 /**
  * The parts of the state necessary for rendering
  */
-export type StateView = { vertical_borders: Array<Array<boolean>>, horizontal_borders: Array<Array<boolean>>, objective: Objective, error: BoardError | null, has_future: boolean, has_past: boolean, is_solved: boolean, };
+export type StateView = { vertical_borders: Array<Array<boolean>>, horizontal_borders: Array<Array<boolean>>, 
+/**
+ * Edges marked as definitely not a wall, in the same layout as the borders
+ */
+vertical_markers: Array<Array<boolean>>, horizontal_markers: Array<Array<boolean>>, objective: Objective, 
+/**
+ * The center each cell is painted into, in half-steps, by row
+ */
+owners: Array<Array<Position | null>>, error: BoardError | null, 
+/**
+ * The walls drawn since the outermost trial began, in the same layout as the borders
+ */
+vertical_trial_walls: Array<Array<boolean>>, horizontal_trial_walls: Array<Array<boolean>>, 
+/**
+ * The number of nested trials in progress
+ */
+trial_depth: number, 
+/**
+ * The number of hints taken, including undone ones
+ */
+hints_used: number, 
+/**
+ * The number of hint walls on the board, which are part of the objective's walls
+ */
+hints_on_board: number, has_future: boolean, has_past: boolean, is_solved: boolean, 
+/**
+ * The active solving time up to the last timestamp, in milliseconds
+ */
+elapsed_ms: number, timer_running: boolean, stats: Stats, completion: CompletionSummary | null, 
+/**
+ * The hint tiers with their costs
+ */
+hint_tiers: Array<HintTierView>, 
+/**
+ * The cells pointed at by the last hint locating a mistake, until the next move
+ */
+mistake_region: Array<Position>, 
+/**
+ * Once the player gave up, how the walls on the board compare with the solution
+ */
+solution_diff: SolutionDiff | null, 
+/**
+ * The keyboard cursor in half-steps, on a cell, an edge or a center between cells
+ */
+cursor: Position, 
+/**
+ * The regions enclosed by walls, and which of them are finished galaxies
+ */
+regions: RegionMap, };
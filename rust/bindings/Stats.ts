@@ -0,0 +1,34 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What the player has done so far
+ */
+export type Stats = { 
+/**
+ * Moves made, not counting undo, redo and hints
+ */
+moves: number, undos: number, 
+/**
+ * The number of times the player checked the solution
+ */
+checks: number, 
+/**
+ * The number of errors found by each check, in order
+ */
+errors_per_check: Array<number>, 
+/**
+ * The number of times the player compared their walls with the solution
+ */
+progress_checks: number, 
+/**
+ * Hints taken of each tier, including undone ones
+ */
+mistakes_located: number, walls_revealed: number, galaxies_revealed: number, 
+/**
+ * The sum of the costs of the hints taken
+ */
+hint_cost: number, 
+/**
+ * Whether the player revealed the solution before solving the puzzle
+ */
+gave_up: boolean, };
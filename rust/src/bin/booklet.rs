@@ -0,0 +1,118 @@
+//! Generates a printable booklet of puzzles as svg pages, with the answers at the end.
+//!
+//! ```text
+//! cargo run --release --bin booklet -- [OPTIONS] [PUZZLE.json...]
+//! ```
+//!
+//! Puzzles are read from the given puzzle documents. Without any, `--count`
+//! puzzles of `--size` are generated.
+
+use laniakea::model::puzzle::Puzzle;
+use laniakea::render::booklet::{render_booklet, BookletOptions};
+use std::path::PathBuf;
+use std::process::exit;
+use std::{env, fs};
+
+const USAGE: &str = "\
+Usage: booklet [OPTIONS] [PUZZLE.json...]
+
+Options:
+  --count <N>       Number of puzzles to generate when no files are given [default: 8]
+  --size <WxH>      Size of the generated puzzles [default: 10x10]
+  --per-page <N>    Number of puzzles on each page [default: 4]
+  --title <TITLE>   Title printed on the puzzle pages
+  --out <DIR>       Directory to write the pages to [default: booklet]
+  --help            Print this message";
+
+struct Arguments {
+    count: usize,
+    width: usize,
+    height: usize,
+    out: PathBuf,
+    files: Vec<PathBuf>,
+    options: BookletOptions,
+}
+
+fn parse_arguments() -> Result<Arguments, String> {
+    let mut arguments = Arguments {
+        count: 8,
+        width: 10,
+        height: 10,
+        out: PathBuf::from("booklet"),
+        files: Vec::new(),
+        options: BookletOptions::default(),
+    };
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("missing value for {arg}"));
+        match arg.as_str() {
+            "--count" => arguments.count = parse_number(&value()?)?,
+            "--size" => {
+                let size = value()?;
+                let (width, height) = size
+                    .split_once('x')
+                    .ok_or(format!("invalid size {size}, expected e.g. 10x10"))?;
+                arguments.width = parse_number(width)?;
+                arguments.height = parse_number(height)?;
+            }
+            "--per-page" => arguments.options.puzzles_per_page = parse_number(&value()?)?,
+            "--title" => arguments.options.title = Some(value()?),
+            "--out" => arguments.out = PathBuf::from(value()?),
+            "--help" => {
+                println!("{USAGE}");
+                exit(0);
+            }
+            _ if arg.starts_with("--") => return Err(format!("unknown option {arg}")),
+            _ => arguments.files.push(PathBuf::from(arg)),
+        }
+    }
+    Ok(arguments)
+}
+
+fn parse_number(value: &str) -> Result<usize, String> {
+    match value.parse() {
+        Ok(number) if number > 0 => Ok(number),
+        _ => Err(format!("expected a positive number, got {value}")),
+    }
+}
+
+fn load_puzzles(arguments: &Arguments) -> Result<Vec<Puzzle>, String> {
+    if arguments.files.is_empty() {
+        return Ok((0..arguments.count)
+            .map(|_| Puzzle::generate(arguments.width, arguments.height))
+            .collect());
+    }
+    arguments
+        .files
+        .iter()
+        .map(|path| {
+            let json = fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+            Puzzle::from_json(&json).map_err(|e| format!("{}: {e}", path.display()))
+        })
+        .collect()
+}
+
+fn run() -> Result<(), String> {
+    let arguments = parse_arguments()?;
+    let puzzles = load_puzzles(&arguments)?;
+    let pages = render_booklet(&puzzles, &arguments.options);
+    fs::create_dir_all(&arguments.out).map_err(|e| format!("{}: {e}", arguments.out.display()))?;
+    for (index, page) in pages.iter().enumerate() {
+        let path = arguments.out.join(format!("page-{:02}.svg", index + 1));
+        fs::write(&path, page).map_err(|e| format!("{}: {e}", path.display()))?;
+    }
+    eprintln!(
+        "Wrote {} pages for {} puzzles to {}",
+        pages.len(),
+        puzzles.len(),
+        arguments.out.display()
+    );
+    Ok(())
+}
+
+fn main() {
+    if let Err(message) = run() {
+        eprintln!("error: {message}\n\n{USAGE}");
+        exit(1);
+    }
+}
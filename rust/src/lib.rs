@@ -2,7 +2,8 @@ use web_sys::wasm_bindgen::prelude::wasm_bindgen;
 use crate::model::game_state::GameState;
 use crate::model::universe::Universe;
 
-mod model;
+pub mod model;
+pub mod render;
 
 #[wasm_bindgen]
 pub fn greet(name: &str) -> String {
@@ -1,10 +1,8 @@
-use crate::model::universe::Universe;
+use laniakea::model::universe::Universe;
 use ordered_float::OrderedFloat;
 use std::thread::sleep;
 use std::time::Duration;
 
-mod model;
-
 fn main() {
     loop {
         let universe = Universe::generate(10, 10);
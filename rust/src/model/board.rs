@@ -4,13 +4,19 @@ use crate::model::galaxy::Galaxy;
 use crate::model::objective::Objective;
 use crate::model::position::{CenterPlacement, Position};
 use itertools::Itertools;
-use std::collections::{BTreeSet, HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+use std::fmt::{Display, Formatter};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Board {
     width: usize,
     height: usize,
     borders: HashSet<Border>,
+    /// Edges the player has marked as definitely not a wall. A marker is kept when a wall
+    /// is drawn over it, but the wall takes precedence.
+    #[serde(default)]
+    markers: HashSet<Border>,
 }
 
 impl Board {
@@ -19,15 +25,29 @@ impl Board {
             width,
             height,
             borders: HashSet::new(),
+            markers: HashSet::new(),
         }
     }
 
+    /// Creates a board with the given walls
+    pub fn with_walls(
+        width: usize,
+        height: usize,
+        walls: impl IntoIterator<Item = Border>,
+    ) -> Self {
+        let mut board = Board::new(width, height);
+        for wall in walls {
+            board.add_wall(wall.p1(), wall.p2());
+        }
+        board
+    }
+
     pub fn get_width(&self) -> usize {
         self.width
     }
 
     pub fn get_height(&self) -> usize {
-        self.width
+        self.height
     }
 
     pub fn contains(&self, position: &Position) -> bool {
@@ -37,6 +57,11 @@ impl Board {
             && position.column < self.width as i32
     }
 
+    /// Returns true iff both sides of the border are on the board
+    pub fn contains_border(&self, border: &Border) -> bool {
+        self.contains(&border.p1()) && self.contains(&border.p2())
+    }
+
     fn get_positions(&self) -> impl Iterator<Item = Position> + use<'_> {
         (0..self.height).into_iter().flat_map(move |row| {
             (0..self.width)
@@ -85,117 +110,191 @@ impl Board {
         self.borders.iter().copied()
     }
 
-    fn get_galaxies(&self) -> Vec<Galaxy> {
-        let mut galaxies = Vec::new();
-        let mut remaining_positions: BTreeSet<Position> = self.get_positions().collect();
-        while let Some(p) = remaining_positions.pop_first() {
-            let mut component = HashSet::new();
-            let mut queue = BTreeSet::new();
-            queue.insert(p);
-            while let Some(p) = queue.pop_first() {
-                component.insert(p);
-                remaining_positions.remove(&p);
-                for neighbour in p.adjacent() {
-                    if component.contains(&neighbour) {
-                        continue;
-                    }
-                    if queue.contains(&neighbour) {
-                        continue;
-                    }
-                    if !self.contains(&neighbour) {
-                        continue;
-                    }
-                    if self.is_wall(p, neighbour) {
-                        continue;
-                    }
-                    queue.insert(neighbour);
+    /// Returns true iff the edge is marked as open and there's no wall on it
+    pub fn is_marked(&self, border: &Border) -> bool {
+        self.markers.contains(border) && !self.is_active(border)
+    }
+
+    /// Toggles the marker on the edge, returns true if there's a marker after the toggle
+    pub fn toggle_marker(&mut self, border: &Border) -> bool {
+        debug_assert!(self.contains_border(border));
+        if self.markers.remove(border) {
+            false
+        } else {
+            self.markers.insert(*border);
+            true
+        }
+    }
+
+    pub fn get_markers(&self) -> impl Iterator<Item = Border> + use<'_> {
+        self.markers.iter().copied()
+    }
+
+    /// Returns the regions of the board separated by walls
+    pub fn get_galaxies(&self) -> Vec<Galaxy> {
+        self.get_regions(self.get_positions())
+    }
+
+    /// Returns the distinct regions containing the given cells
+    pub fn get_regions(&self, cells: impl IntoIterator<Item = Position>) -> Vec<Galaxy> {
+        let mut regions = Vec::new();
+        let mut visited = HashSet::new();
+        for cell in cells {
+            if visited.contains(&cell) || !self.contains(&cell) {
+                continue;
+            }
+            let region = self.get_region(&cell);
+            visited.extend(region.iter().copied());
+            regions.push(Galaxy::from(region));
+        }
+        regions
+    }
+
+    /// Returns the cells reachable from the start without crossing a wall
+    fn get_region(&self, start: &Position) -> HashSet<Position> {
+        let mut component = HashSet::new();
+        let mut queue = BTreeSet::new();
+        queue.insert(*start);
+        while let Some(p) = queue.pop_first() {
+            component.insert(p);
+            for neighbour in p.adjacent() {
+                if component.contains(&neighbour) {
+                    continue;
+                }
+                if queue.contains(&neighbour) {
+                    continue;
+                }
+                if !self.contains(&neighbour) {
+                    continue;
                 }
+                if self.is_wall(p, neighbour) {
+                    continue;
+                }
+                queue.insert(neighbour);
             }
-            galaxies.push(Galaxy::from(component));
         }
+        component
+    }
 
-        galaxies
+    /// Returns a cell next to the center, whose galaxy is the galaxy of the center
+    fn get_center_cell(center: &Position) -> Position {
+        match center.get_center_placement() {
+            CenterPlacement::Center(p) => p,
+            CenterPlacement::VerticalBorder(b) => b.p1(),
+            CenterPlacement::HorizontalBorder(b) => b.p1(),
+            CenterPlacement::Intersection(r) => r.top_left(),
+        }
     }
 
     pub fn compute_error(&self, objective: &Objective) -> BoardError {
-        let dangling_borders = self.get_dangling_borders().collect();
+        let mut error = BoardError {
+            dangling_borders: self.get_dangling_borders().collect(),
+            cut_centers: objective
+                .centers
+                .iter()
+                .map(|gc| gc.position)
+                .filter(|center| self.is_center_cut(center))
+                .collect(),
+            ..BoardError::none()
+        };
+        for galaxy in self.get_galaxies() {
+            self.add_galaxy_errors(&mut error, objective, &galaxy);
+        }
+        error
+    }
 
-        let galaxies = self.get_galaxies();
-        let galaxy_by_position: HashMap<Position, &Galaxy> = galaxies
+    /// Updates errors computed by [Board::compute_error] after the given walls were toggled.
+    /// Only the borders around the toggled walls and the galaxies on both sides of them are
+    /// checked again, the rest of the board is assumed to be unchanged.
+    pub fn update_error(&self, error: &mut BoardError, objective: &Objective, toggled: &[Border]) {
+        let cells: HashSet<Position> = toggled
             .iter()
-            .flat_map(|galaxy| galaxy.get_positions().copied().map(move |p| (p, galaxy)))
-            .collect();
-        let galaxy_by_objective_center: HashMap<Position, &Galaxy> = objective
-            .centers
-            .iter()
-            .map(|gc| {
-                let some_position_around_center = match gc.position.get_center_placement() {
-                    CenterPlacement::Center(p) => p,
-                    CenterPlacement::VerticalBorder(b) => b.p1(),
-                    CenterPlacement::HorizontalBorder(b) => b.p1(),
-                    CenterPlacement::Intersection(r) => r.top_left(),
-                };
-                let &galaxy = galaxy_by_position
-                    .get(&some_position_around_center)
-                    .unwrap();
-                (gc.position, galaxy)
-            })
+            .flat_map(|border| [border.p1(), border.p2()])
+            .filter(|cell| self.contains(cell))
             .collect();
 
-        let cut_centers: HashSet<Position> = objective
-            .centers
-            .iter()
-            .map(|gc| gc.position)
-            .filter(|center| self.is_center_cut(center))
-            .collect();
+        // A wall is dangling depending on the walls at its ends, so only walls that
+        // share an end with a toggled wall can change
+        for border in self.get_nearby_borders(&cells) {
+            if self.is_active(&border) && self.is_dangling(&border) {
+                error.dangling_borders.insert(border);
+            } else {
+                error.dangling_borders.remove(&border);
+            }
+        }
 
-        let incorrect_galaxy_sizes = objective
-            .centers
-            .iter()
-            .filter_map(|gc| {
-                if let Some(size) = gc.size {
-                    let galaxy = galaxy_by_objective_center.get(&gc.position).unwrap();
-                    if galaxy.size() != size {
-                        Some(gc.position)
-                    } else {
-                        None
-                    }
+        for gc in &objective.centers {
+            let placement = gc.position.get_center_placement().get_positions();
+            if placement.iter().any(|cell| cells.contains(cell)) {
+                if self.is_center_cut(&gc.position) {
+                    error.cut_centers.insert(gc.position);
                 } else {
-                    None
+                    error.cut_centers.remove(&gc.position);
                 }
-            })
-            .collect();
+            }
+        }
 
-        let asymmetric_centers = objective
-            .centers
+        let galaxies = self.get_regions(cells);
+        let affected: HashSet<Position> = galaxies
             .iter()
-            .filter_map(|gc| {
-                let galaxy = galaxy_by_objective_center.get(&gc.position).unwrap();
-                if galaxy.center() != gc.position || !galaxy.is_valid() {
-                    Some(gc.position)
-                } else {
-                    None
-                }
-            })
-            .collect();
-
-        let centerfull_cells: HashSet<Position> = galaxy_by_objective_center
-            .values()
             .flat_map(|galaxy| galaxy.get_positions())
             .copied()
             .collect();
-        let centerless_cells = self
-            .get_positions()
-            .filter(|p| !centerfull_cells.contains(p))
-            .collect();
+        error
+            .centerless_cells
+            .retain(|cell| !affected.contains(cell));
+        for gc in &objective.centers {
+            if affected.contains(&Board::get_center_cell(&gc.position)) {
+                error.incorrect_galaxy_sizes.remove(&gc.position);
+                error.asymmetric_centers.remove(&gc.position);
+            }
+        }
+        for galaxy in &galaxies {
+            self.add_galaxy_errors(error, objective, galaxy);
+        }
+    }
+
+    /// Adds the errors of a single galaxy: the sizes and symmetry of its centers,
+    /// or its cells if it has no center
+    fn add_galaxy_errors(&self, error: &mut BoardError, objective: &Objective, galaxy: &Galaxy) {
+        let centers = objective
+            .centers
+            .iter()
+            .filter(|gc| galaxy.contains_position(&Board::get_center_cell(&gc.position)));
+        let mut has_center = false;
+        for gc in centers {
+            has_center = true;
+            if gc.size.is_some_and(|size| galaxy.size() != size) {
+                error.incorrect_galaxy_sizes.insert(gc.position);
+            }
+            if galaxy.center() != gc.position || !galaxy.is_valid() {
+                error.asymmetric_centers.insert(gc.position);
+            }
+        }
+        if !has_center {
+            error
+                .centerless_cells
+                .extend(galaxy.get_positions().copied());
+        }
+    }
 
-        BoardError {
-            dangling_borders,
-            incorrect_galaxy_sizes,
-            centerless_cells,
-            cut_centers,
-            asymmetric_centers,
+    /// Returns the borders on the board between cells next to, or diagonal to, the given cells
+    fn get_nearby_borders(&self, cells: &HashSet<Position>) -> HashSet<Border> {
+        let mut borders = HashSet::new();
+        for cell in cells {
+            for row in cell.row - 1..=cell.row + 1 {
+                for column in cell.column - 1..=cell.column + 1 {
+                    let p = Position::new(row, column);
+                    for neighbour in [p.right(), p.down()] {
+                        let border = Border::new(p, neighbour);
+                        if self.contains_border(&border) {
+                            borders.insert(border);
+                        }
+                    }
+                }
+            }
         }
+        borders
     }
 
     fn is_center_cut(&self, center: &Position) -> bool {
@@ -301,6 +400,252 @@ impl Board {
         }
         matrix
     }
+
+    /// Like [Board::get_vertical_borders], for the markers that aren't hidden under a wall
+    pub fn get_vertical_markers(&self) -> Vec<Vec<bool>> {
+        self.get_vertical_matrix(self.get_markers().filter(|marker| self.is_marked(marker)))
+    }
+
+    /// Like [Board::get_horizontal_borders], for the markers that aren't hidden under a wall
+    pub fn get_horizontal_markers(&self) -> Vec<Vec<bool>> {
+        self.get_horizontal_matrix(self.get_markers().filter(|marker| self.is_marked(marker)))
+    }
+
+    /// Lays out the vertical ones among the borders like [Board::get_vertical_borders]
+    pub fn get_vertical_matrix(&self, borders: impl IntoIterator<Item = Border>) -> Vec<Vec<bool>> {
+        let mut matrix = vec![vec![false; self.width - 1]; self.height];
+        for border in borders {
+            if border.is_vertical() {
+                matrix[border.p1().row as usize][border.p1().column as usize] = true;
+            }
+        }
+        matrix
+    }
+
+    /// Lays out the horizontal ones among the borders like [Board::get_horizontal_borders]
+    pub fn get_horizontal_matrix(
+        &self,
+        borders: impl IntoIterator<Item = Border>,
+    ) -> Vec<Vec<bool>> {
+        let mut matrix = vec![vec![false; self.width]; self.height - 1];
+        for border in borders {
+            if border.is_horizontal() {
+                matrix[border.p1().row as usize][border.p1().column as usize] = true;
+            }
+        }
+        matrix
+    }
+
+    /// Returns a box-drawing rendering of the board with the centers of the objective,
+    /// highlighting the errors if given, see [BoardDisplay]
+    pub fn display<'a>(
+        &'a self,
+        objective: &'a Objective,
+        error: Option<&'a BoardError>,
+    ) -> BoardDisplay<'a> {
+        BoardDisplay {
+            board: self,
+            objective,
+            error,
+        }
+    }
+
+    pub fn render(&self, objective: &Objective, error: Option<&BoardError>) -> String {
+        self.display(objective, error).to_string()
+    }
+}
+
+/// Draws a board with box-drawing characters. Every cell is three characters wide,
+/// and centers are drawn where they lie, on cells, borders or intersections:
+/// - `●` is a center, or its size if it has one and there's room for it
+/// - `✕` is a center cut by a wall
+/// - `○` is a center whose galaxy is asymmetric or has the wrong size
+/// - `━` and `┃` are dangling walls
+/// - `░░░` is a cell that doesn't belong to the galaxy of any center
+pub struct BoardDisplay<'a> {
+    board: &'a Board,
+    objective: &'a Objective,
+    error: Option<&'a BoardError>,
+}
+
+impl BoardDisplay<'_> {
+    /// Returns the glyph to draw at the given half-step position, if there's a center there
+    fn get_center_glyph(&self, position: &Position, room: usize) -> Option<String> {
+        let center = self
+            .objective
+            .centers
+            .iter()
+            .find(|gc| gc.position == *position)?;
+        let error = self.error;
+        let glyph = if error.is_some_and(|e| e.cut_centers.contains(position)) {
+            "✕".to_string()
+        } else if error.is_some_and(|e| {
+            e.asymmetric_centers.contains(position) || e.incorrect_galaxy_sizes.contains(position)
+        }) {
+            "○".to_string()
+        } else {
+            center
+                .size
+                .map(|size| size.to_string())
+                .filter(|size| size.len() <= room)
+                .unwrap_or("●".to_string())
+        };
+        Some(glyph)
+    }
+
+    fn is_dangling(&self, border: &Border) -> bool {
+        self.error
+            .is_some_and(|error| error.dangling_borders.contains(border))
+    }
+
+    /// Returns whether there's a wall to the left of the given cell,
+    /// where the leftmost and rightmost walls are the outer frame
+    fn is_vertical_wall(&self, row: i32, column: i32) -> bool {
+        column == 0
+            || column == self.board.width as i32
+            || self
+                .board
+                .is_wall(Position::new(row, column - 1), Position::new(row, column))
+    }
+
+    /// Returns whether there's a wall above the given cell,
+    /// where the topmost and bottommost walls are the outer frame
+    fn is_horizontal_wall(&self, row: i32, column: i32) -> bool {
+        row == 0
+            || row == self.board.height as i32
+            || self
+                .board
+                .is_wall(Position::new(row - 1, column), Position::new(row, column))
+    }
+
+    /// Writes the intersection above and to the left of the given cell
+    fn write_intersection(&self, f: &mut Formatter<'_>, row: i32, column: i32) -> std::fmt::Result {
+        let center = Position::new(2 * row - 1, 2 * column - 1);
+        if let Some(glyph) = self.get_center_glyph(&center, 1) {
+            return write!(f, "{glyph}");
+        }
+        let height = self.board.height as i32;
+        let width = self.board.width as i32;
+        let bar_top = row > 0 && self.is_vertical_wall(row - 1, column);
+        let bar_right = column < width && self.is_horizontal_wall(row, column);
+        let bar_bottom = row < height && self.is_vertical_wall(row, column);
+        let bar_left = column > 0 && self.is_horizontal_wall(row, column - 1);
+        let corner = match (bar_top, bar_right, bar_bottom, bar_left) {
+            (false, false, false, false) => ' ',
+            (false, false, false, true) => '╴',
+            (false, false, true, false) => '╷',
+            (false, false, true, true) => '┐',
+            (false, true, false, false) => '╶',
+            (false, true, false, true) => '─',
+            (false, true, true, false) => '┌',
+            (false, true, true, true) => '┬',
+            (true, false, false, false) => '╵',
+            (true, false, false, true) => '┘',
+            (true, false, true, false) => '│',
+            (true, false, true, true) => '┤',
+            (true, true, false, false) => '└',
+            (true, true, false, true) => '┴',
+            (true, true, true, false) => '├',
+            (true, true, true, true) => '┼',
+        };
+        write!(f, "{corner}")
+    }
+
+    /// Writes the border above the given cell
+    fn write_horizontal_border(
+        &self,
+        f: &mut Formatter<'_>,
+        row: i32,
+        column: i32,
+    ) -> std::fmt::Result {
+        let bar = if !self.is_horizontal_wall(row, column) {
+            ' '
+        } else if row > 0
+            && row < self.board.height as i32
+            && self.is_dangling(&Border::new(
+                Position::new(row - 1, column),
+                Position::new(row, column),
+            ))
+        {
+            '━'
+        } else {
+            '─'
+        };
+        let center = Position::new(2 * row - 1, 2 * column);
+        match self.get_center_glyph(&center, 1) {
+            Some(glyph) => write!(f, "{bar}{glyph}{bar}"),
+            None => write!(f, "{bar}{bar}{bar}"),
+        }
+    }
+
+    /// Writes the border to the left of the given cell
+    fn write_vertical_border(
+        &self,
+        f: &mut Formatter<'_>,
+        row: i32,
+        column: i32,
+    ) -> std::fmt::Result {
+        let center = Position::new(2 * row, 2 * column - 1);
+        if let Some(glyph) = self.get_center_glyph(&center, 1) {
+            return write!(f, "{glyph}");
+        }
+        let bar = if !self.is_vertical_wall(row, column) {
+            ' '
+        } else if column > 0
+            && column < self.board.width as i32
+            && self.is_dangling(&Border::new(
+                Position::new(row, column - 1),
+                Position::new(row, column),
+            ))
+        {
+            '┃'
+        } else {
+            '│'
+        };
+        write!(f, "{bar}")
+    }
+
+    fn write_cell(&self, f: &mut Formatter<'_>, row: i32, column: i32) -> std::fmt::Result {
+        let cell = Position::new(row, column);
+        let center = Position::new(2 * row, 2 * column);
+        if let Some(glyph) = self.get_center_glyph(&center, 3) {
+            write!(f, "{glyph:^3}")
+        } else if self
+            .error
+            .is_some_and(|error| error.centerless_cells.contains(&cell))
+        {
+            write!(f, "░░░")
+        } else {
+            write!(f, "   ")
+        }
+    }
+}
+
+impl Display for BoardDisplay<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let height = self.board.height as i32;
+        let width = self.board.width as i32;
+        for row in 0..=height {
+            for column in 0..=width {
+                self.write_intersection(f, row, column)?;
+                if column < width {
+                    self.write_horizontal_border(f, row, column)?;
+                }
+            }
+            if row == height {
+                break;
+            }
+            writeln!(f)?;
+            for column in 0..=width {
+                self.write_vertical_border(f, row, column)?;
+                if column < width {
+                    self.write_cell(f, row, column)?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -317,4 +662,159 @@ mod tests {
             assert_eq!(galaxies[0].size(), 1);
         }
     }
+
+    mod update_error {
+        use crate::model::board::Board;
+        use crate::model::border::Border;
+        use crate::model::galaxy::Galaxy;
+        use crate::model::objective::Objective;
+        use crate::model::position::Position;
+        use crate::model::universe::Universe;
+        use proptest::proptest;
+
+        fn objective() -> Objective {
+            let galaxies = [
+                Galaxy::from([(0, 0), (0, 1), (1, 0), (1, 1)]),
+                Galaxy::from([(0, 2), (0, 3)]),
+                Galaxy::from([(1, 2), (1, 3)]),
+                Galaxy::from([
+                    (2, 0),
+                    (2, 1),
+                    (2, 2),
+                    (2, 3),
+                    (3, 0),
+                    (3, 1),
+                    (3, 2),
+                    (3, 3),
+                ]),
+            ];
+            let mut objective = Objective::generate(&Universe::from(galaxies.as_slice()));
+            objective.centers = objective
+                .centers
+                .into_iter()
+                .map(|mut gc| {
+                    gc.size = Some(4);
+                    gc
+                })
+                .collect();
+            objective
+        }
+
+        proptest! {
+            #[test]
+            fn should_match_computing_all_errors(
+                toggles in proptest::collection::vec((0..4i32, 0..4i32, proptest::bool::ANY), 1..40)
+            ) {
+                let objective = objective();
+                let mut board = Board::new(4, 4);
+                let mut error = board.compute_error(&objective);
+                for (row, column, vertical) in toggles {
+                    let p1 = Position::new(row, column);
+                    let p2 = if vertical { p1.right() } else { p1.down() };
+                    if !board.contains(&p2) {
+                        continue;
+                    }
+                    board.toggle_wall(p1, p2);
+                    board.update_error(&mut error, &objective, &[Border::new(p1, p2)]);
+                    assert_eq!(error, board.compute_error(&objective));
+                }
+            }
+        }
+    }
+
+    mod display {
+        use crate::model::board::Board;
+        use crate::model::board_error::BoardError;
+        use crate::model::border::Border;
+        use crate::model::objective::{GalaxyCenter, Objective};
+        use crate::model::position::Position;
+        use indoc::indoc;
+        use std::collections::HashSet;
+
+        fn objective(centers: &[((i32, i32), Option<usize>)]) -> Objective {
+            Objective {
+                centers: centers
+                    .iter()
+                    .map(|&(position, size)| GalaxyCenter {
+                        position: Position::from(position),
+                        size,
+                    })
+                    .collect(),
+                walls: HashSet::new(),
+            }
+        }
+
+        #[test]
+        fn solved_board() {
+            let mut board = Board::new(3, 2);
+            board.add_wall(Position::new(0, 0), Position::new(1, 0));
+            board.add_wall(Position::new(0, 1), Position::new(1, 1));
+            board.add_wall(Position::new(0, 1), Position::new(0, 2));
+            board.add_wall(Position::new(1, 1), Position::new(1, 2));
+            let objective = objective(&[((0, 1), None), ((1, 4), Some(2)), ((2, 1), None)]);
+            assert!(board.compute_error(&objective).is_error_free());
+            assert_eq!(
+                board.render(&objective, None),
+                indoc! {"
+                    ┌───────┬───┐
+                    │   ●   │   │
+                    ├───────┤ 2 │
+                    │   ●   │   │
+                    └───────┴───┘"
+                }
+            );
+        }
+
+        #[test]
+        fn sized_center_in_cell() {
+            let board = Board::new(1, 1);
+            let objective = objective(&[((0, 0), Some(1))]);
+            assert_eq!(
+                board.render(&objective, None),
+                indoc! {"
+                    ┌───┐
+                    │ 1 │
+                    └───┘"
+                }
+            );
+        }
+
+        #[test]
+        fn errors() {
+            let mut board = Board::new(2, 2);
+            board.add_wall(Position::new(0, 0), Position::new(0, 1));
+            board.add_wall(Position::new(0, 1), Position::new(1, 1));
+            let objective = objective(&[((0, 0), None), ((1, 2), None)]);
+            let error = BoardError {
+                dangling_borders: HashSet::from([Border::new(
+                    Position::new(0, 0),
+                    Position::new(0, 1),
+                )]),
+                centerless_cells: HashSet::from([Position::new(1, 0)]),
+                cut_centers: HashSet::from([Position::new(1, 2)]),
+                asymmetric_centers: HashSet::from([Position::new(0, 0)]),
+                ..BoardError::none()
+            };
+            assert_eq!(
+                board.render(&objective, Some(&error)),
+                indoc! {"
+                    ┌───┬───┐
+                    │ ○ ┃   │
+                    │   └─✕─┤
+                    │░░░    │
+                    └───────┘"
+                }
+            );
+            assert_eq!(
+                board.render(&objective, None),
+                indoc! {"
+                    ┌───┬───┐
+                    │ ● │   │
+                    │   └─●─┤
+                    │       │
+                    └───────┘"
+                }
+            );
+        }
+    }
 }
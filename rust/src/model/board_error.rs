@@ -1,16 +1,18 @@
 use crate::model::border::Border;
 use crate::model::position::Position;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use ts_rs::TS;
 
-#[derive(Debug, Default, Clone, Serialize, TS)]
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize, Deserialize, TS)]
 pub struct BoardError {
     pub dangling_borders: HashSet<Border>,
     pub incorrect_galaxy_sizes: HashSet<Position>,
     pub centerless_cells: HashSet<Position>,
     pub cut_centers: HashSet<Position>,
     pub asymmetric_centers: HashSet<Position>,
+    /// Cells painted into a center that isn't in their galaxy
+    pub misassigned_cells: HashSet<Position>,
 }
 
 impl BoardError {
@@ -18,11 +20,22 @@ impl BoardError {
         BoardError::default()
     }
 
+    /// Returns the number of errors of all kinds
+    pub fn count(&self) -> usize {
+        self.dangling_borders.len()
+            + self.incorrect_galaxy_sizes.len()
+            + self.centerless_cells.len()
+            + self.cut_centers.len()
+            + self.asymmetric_centers.len()
+            + self.misassigned_cells.len()
+    }
+
     pub fn is_error_free(&self) -> bool {
         self.dangling_borders.is_empty()
             && self.incorrect_galaxy_sizes.is_empty()
             && self.centerless_cells.is_empty()
             && self.asymmetric_centers.is_empty()
             && self.cut_centers.is_empty()
+            && self.misassigned_cells.is_empty()
     }
 }
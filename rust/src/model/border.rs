@@ -1,6 +1,7 @@
 use crate::model::position::Position;
 use std::cmp::{max, min};
-use serde::Serialize;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize};
 use ts_rs::TS;
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone, Ord, PartialOrd, Hash, Serialize, TS)]
@@ -9,6 +10,24 @@ pub struct Border {
     p2: Position,
 }
 
+/// The serialized shape of a [Border], which is validated before being turned into one
+#[derive(Deserialize)]
+struct RawBorder {
+    p1: Position,
+    p2: Position,
+}
+
+impl<'de> Deserialize<'de> for Border {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let RawBorder { p1, p2 } = RawBorder::deserialize(deserializer)?;
+        if p1.is_adjacent_to(&p2) {
+            Ok(Border::new(p1, p2))
+        } else {
+            Err(D::Error::custom(format!("{p1} and {p2} are not adjacent")))
+        }
+    }
+}
+
 impl Border {
     pub fn new(p1: Position, p2: Position) -> Self {
         debug_assert!(p1.is_adjacent_to(&p2));
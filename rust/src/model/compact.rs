@@ -0,0 +1,177 @@
+use crate::model::border::Border;
+use crate::model::position::Position;
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+
+const WALLS_AS_INDICES: u8 = 0;
+const WALLS_AS_BITS: u8 = 1;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum CompactError {
+    /// The bytes end in the middle of a value
+    Truncated,
+    /// A value can't be read, such as an unknown kind or a varint that's too long
+    InvalidEncoding,
+    /// A wall refers to an edge that isn't on the board
+    WallOutOfBounds(usize),
+}
+
+impl Display for CompactError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompactError::Truncated => write!(f, "data is truncated"),
+            CompactError::InvalidEncoding => write!(f, "data is not validly encoded"),
+            CompactError::WallOutOfBounds(index) => {
+                write!(f, "wall {index} is outside the board")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CompactError {}
+
+/// Numbers the edges between cells of a board, vertical borders first, row by row
+pub struct Edges {
+    width: usize,
+    height: usize,
+}
+
+impl Edges {
+    pub fn new(width: usize, height: usize) -> Self {
+        Edges { width, height }
+    }
+
+    pub fn count(&self) -> usize {
+        self.height * (self.width - 1) + (self.height - 1) * self.width
+    }
+
+    fn vertical_count(&self) -> usize {
+        self.height * (self.width - 1)
+    }
+
+    pub fn index(&self, border: &Border) -> usize {
+        let p1 = border.p1();
+        let (row, column) = (p1.row as usize, p1.column as usize);
+        if border.is_vertical() {
+            row * (self.width - 1) + column
+        } else {
+            self.vertical_count() + row * self.width + column
+        }
+    }
+
+    pub fn border(&self, index: usize) -> Option<Border> {
+        if index < self.vertical_count() {
+            let p1 = Position::from((index / (self.width - 1), index % (self.width - 1)));
+            Some(Border::new(p1, p1.right()))
+        } else if index < self.count() {
+            let index = index - self.vertical_count();
+            let p1 = Position::from((index / self.width, index % self.width));
+            Some(Border::new(p1, p1.down()))
+        } else {
+            None
+        }
+    }
+
+    pub fn write_walls(&self, bytes: &mut Vec<u8>, walls: &HashSet<Border>) {
+        let mut indices: Vec<usize> = walls.iter().map(|wall| self.index(wall)).collect();
+        indices.sort();
+
+        let mut as_indices = Vec::new();
+        write_varint(&mut as_indices, indices.len());
+        for index in &indices {
+            write_varint(&mut as_indices, *index);
+        }
+
+        let mut as_bits = vec![0; self.count().div_ceil(8)];
+        for index in &indices {
+            as_bits[index / 8] |= 1 << (index % 8);
+        }
+
+        if as_indices.len() <= as_bits.len() {
+            bytes.push(WALLS_AS_INDICES);
+            bytes.append(&mut as_indices);
+        } else {
+            bytes.push(WALLS_AS_BITS);
+            bytes.append(&mut as_bits);
+        }
+    }
+
+    pub fn read_walls(&self, reader: &mut Reader) -> Result<HashSet<Border>, CompactError> {
+        let mut walls = HashSet::new();
+        match reader.read_byte()? {
+            WALLS_AS_INDICES => {
+                let count = reader.read_varint()?;
+                for _ in 0..count {
+                    let index = reader.read_varint()?;
+                    let border = self
+                        .border(index)
+                        .ok_or(CompactError::WallOutOfBounds(index))?;
+                    walls.insert(border);
+                }
+            }
+            WALLS_AS_BITS => {
+                let bits = reader.read_bytes(self.count().div_ceil(8))?;
+                for index in 0..bits.len() * 8 {
+                    if bits[index / 8] & (1 << (index % 8)) != 0 {
+                        let border = self
+                            .border(index)
+                            .ok_or(CompactError::WallOutOfBounds(index))?;
+                        walls.insert(border);
+                    }
+                }
+            }
+            _ => return Err(CompactError::InvalidEncoding),
+        }
+        Ok(walls)
+    }
+}
+
+/// Reads the bytes of a compact format from the front
+pub struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Reader { bytes }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    pub fn read_byte(&mut self) -> Result<u8, CompactError> {
+        let (&byte, rest) = self.bytes.split_first().ok_or(CompactError::Truncated)?;
+        self.bytes = rest;
+        Ok(byte)
+    }
+
+    pub fn read_bytes(&mut self, count: usize) -> Result<&'a [u8], CompactError> {
+        if self.bytes.len() < count {
+            return Err(CompactError::Truncated);
+        }
+        let (bytes, rest) = self.bytes.split_at(count);
+        self.bytes = rest;
+        Ok(bytes)
+    }
+
+    pub fn read_varint(&mut self) -> Result<usize, CompactError> {
+        let mut value: usize = 0;
+        for shift in (0..usize::BITS).step_by(7) {
+            let byte = self.read_byte()?;
+            value |= ((byte & 0x7f) as usize) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(CompactError::InvalidEncoding)
+    }
+}
+
+pub fn write_varint(bytes: &mut Vec<u8>, mut value: usize) {
+    while value >= 0x80 {
+        bytes.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
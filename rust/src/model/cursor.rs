@@ -0,0 +1,181 @@
+use crate::model::board::Board;
+use crate::model::border::Border;
+use crate::model::position::{CenterPlacement, Position};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::wasm_bindgen;
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Returns the position next to the given one in this direction
+    pub fn next(&self, position: &Position) -> Position {
+        match self {
+            Direction::Up => position.up(),
+            Direction::Right => position.right(),
+            Direction::Down => position.down(),
+            Direction::Left => position.left(),
+        }
+    }
+}
+
+/// The keyboard cursor, in half-steps like the centers. It rests on cells and on the edges
+/// between them, and only reaches the corners between four cells by jumping to a center.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Cursor {
+    position: Position,
+}
+
+impl Default for Cursor {
+    /// Starts on the top left cell
+    fn default() -> Self {
+        Cursor {
+            position: Position::ZERO,
+        }
+    }
+}
+
+impl Cursor {
+    pub fn get_position(&self) -> Position {
+        self.position
+    }
+
+    /// Returns true iff the half-step position is on a cell, an edge or a corner of the board
+    fn contains(board: &Board, position: &Position) -> bool {
+        position.row >= 0
+            && position.row < 2 * board.get_height() as i32 - 1
+            && position.column >= 0
+            && position.column < 2 * board.get_width() as i32 - 1
+    }
+
+    fn is_corner(position: &Position) -> bool {
+        position.row % 2 == 1 && position.column % 2 == 1
+    }
+
+    /// Moves half a step in the direction, onto the next cell or edge. Corners are stepped
+    /// over. Returns false and stays if that would leave the board.
+    pub fn step(&mut self, board: &Board, direction: Direction) -> bool {
+        let mut next = direction.next(&self.position);
+        if Cursor::is_corner(&next) {
+            next = direction.next(&next);
+        }
+        self.move_to(board, next)
+    }
+
+    /// Moves a whole cell in the direction, staying on a cell or an edge of the same kind.
+    /// Returns false and stays if that would leave the board.
+    pub fn jump(&mut self, board: &Board, direction: Direction) -> bool {
+        let next = direction.next(&direction.next(&self.position));
+        self.move_to(board, next)
+    }
+
+    fn move_to(&mut self, board: &Board, position: Position) -> bool {
+        if !Cursor::contains(board, &position) {
+            return false;
+        }
+        self.position = position;
+        true
+    }
+
+    /// Moves to the next center after the cursor in reading order, or the previous one
+    /// if not forward, wrapping around. Returns false if there are no centers.
+    pub fn jump_to_center(
+        &mut self,
+        centers: impl IntoIterator<Item = Position>,
+        forward: bool,
+    ) -> bool {
+        let mut centers: Vec<Position> = centers.into_iter().collect();
+        centers.sort();
+        let next = if forward {
+            centers
+                .iter()
+                .find(|&&center| center > self.position)
+                .or(centers.first())
+        } else {
+            centers
+                .iter()
+                .rev()
+                .find(|&&center| center < self.position)
+                .or(centers.last())
+        };
+        match next {
+            Some(center) => {
+                self.position = *center;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the edge the keyboard toggles in the direction: on a cell, the edge on that
+    /// side of it, and on an edge, the edge itself. None if there's no such edge on the board.
+    pub fn get_edge(&self, board: &Board, direction: Direction) -> Option<Border> {
+        let edge = match self.position.get_center_placement() {
+            CenterPlacement::Center(cell) => Border::new(cell, direction.next(&cell)),
+            CenterPlacement::VerticalBorder(edge) | CenterPlacement::HorizontalBorder(edge) => edge,
+            CenterPlacement::Intersection(_) => return None,
+        };
+        board.contains_border(&edge).then_some(edge)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::board::Board;
+    use crate::model::border::Border;
+    use crate::model::cursor::{Cursor, Direction};
+    use crate::model::position::Position;
+
+    #[test]
+    fn steps_should_alternate_cells_and_edges_and_skip_corners() {
+        let board = Board::new(2, 2);
+        let mut cursor = Cursor::default();
+        assert!(cursor.step(&board, Direction::Right));
+        assert_eq!(cursor.get_position(), Position::new(0, 1));
+        assert!(cursor.step(&board, Direction::Down));
+        assert_eq!(cursor.get_position(), Position::new(2, 1));
+        assert!(!cursor.step(&board, Direction::Down));
+        assert!(cursor.step(&board, Direction::Right));
+        assert!(!cursor.step(&board, Direction::Right));
+        assert!(!cursor.jump(&board, Direction::Down));
+        assert!(cursor.jump(&board, Direction::Up));
+        assert_eq!(cursor.get_position(), Position::new(0, 2));
+    }
+
+    #[test]
+    fn edges_should_be_on_the_side_of_cells_or_under_the_cursor() {
+        let board = Board::new(2, 2);
+        let mut cursor = Cursor::default();
+        assert_eq!(cursor.get_edge(&board, Direction::Up), None);
+        assert_eq!(
+            cursor.get_edge(&board, Direction::Down),
+            Some(Border::new(Position::new(0, 0), Position::new(1, 0)))
+        );
+        cursor.step(&board, Direction::Right);
+        assert_eq!(
+            cursor.get_edge(&board, Direction::Up),
+            Some(Border::new(Position::new(0, 0), Position::new(0, 1)))
+        );
+    }
+
+    #[test]
+    fn center_jumps_should_wrap_around() {
+        let centers = [Position::new(1, 1), Position::new(0, 2)];
+        let mut cursor = Cursor::default();
+        assert!(cursor.jump_to_center(centers, true));
+        assert_eq!(cursor.get_position(), Position::new(0, 2));
+        assert!(cursor.jump_to_center(centers, true));
+        assert_eq!(cursor.get_position(), Position::new(1, 1));
+        assert!(cursor.jump_to_center(centers, true));
+        assert_eq!(cursor.get_position(), Position::new(0, 2));
+        assert!(cursor.jump_to_center(centers, false));
+        assert_eq!(cursor.get_position(), Position::new(1, 1));
+        assert!(!cursor.jump_to_center([], true));
+    }
+}
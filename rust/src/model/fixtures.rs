@@ -0,0 +1,14 @@
+//! Small puzzles shared by the tests of several modules
+
+use crate::model::galaxy::Galaxy;
+use crate::model::universe::Universe;
+
+/// A 3 by 2 board with a 2 by 2 galaxy to the left and two singletons to the right
+pub fn small_universe() -> Universe {
+    let galaxies = [
+        Galaxy::from([(0, 0), (0, 1), (1, 0), (1, 1)]),
+        Galaxy::from([(0, 2)]),
+        Galaxy::from([(1, 2)]),
+    ];
+    Universe::from(galaxies.as_slice())
+}
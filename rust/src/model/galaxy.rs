@@ -5,6 +5,7 @@ use crate::model::tree::Tree;
 use crate::model::vec2::Vec2;
 use itertools::Itertools;
 use ordered_float::{Float, OrderedFloat};
+use serde::{Deserialize, Serialize};
 use std::cmp::{max, min};
 use std::collections::hash_map::Entry;
 use std::collections::hash_map::Entry::{Occupied, Vacant};
@@ -14,7 +15,7 @@ use std::fmt::{Display, Formatter};
 use std::ops::Sub;
 use std::slice::Iter;
 
-#[derive(Clone, Eq, PartialEq, Debug)]
+#[derive(Clone, Eq, PartialEq, Debug, Default, Serialize, Deserialize)]
 pub struct Galaxy {
     positions: HashSet<Position>,
 }
@@ -1,16 +1,36 @@
 use crate::model::board::Board;
 use crate::model::board_error::BoardError;
 use crate::model::border::Border;
-use crate::model::history::{History, HistoryEntry};
+use crate::model::cursor::{Cursor, Direction};
+use crate::model::hints;
+use crate::model::hints::{HintTier, HintTierView};
+use crate::model::history::{CellChange, HintUndo, History, HistoryEntry};
+use crate::model::input_error;
+use crate::model::input_error::{InputError, InputErrorCode};
+use crate::model::input_rules;
+use crate::model::input_rules::{MoveRejection, Strictness};
 use crate::model::objective::Objective;
+use crate::model::ownership::Ownership;
 use crate::model::position::Position;
+use crate::model::progress::{ProgressReport, SolutionDiff};
+use crate::model::puzzle::{Difficulty, Puzzle};
+use crate::model::regions::RegionMap;
+use crate::model::saved_game::{SaveError, SavedGame, SAVE_VERSION};
+use crate::model::share_code::{ShareCodeError, SharedPuzzle};
+use crate::model::solver::{Solvability, Solver};
+use crate::model::stats::{CompletionSummary, Stats, Timer};
+use crate::model::trial::Trial;
 use crate::model::universe::Universe;
+use crate::render::svg;
+use crate::render::svg::SvgOptions;
+use itertools::Itertools;
 use rand::prelude::IteratorRandom;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
 use ts_rs::TS;
 use wasm_bindgen::prelude::wasm_bindgen;
-use wasm_bindgen::{JsValue, UnwrapThrowExt};
-use HistoryEntry::ToggleBorder;
+use wasm_bindgen::{JsError, JsValue, UnwrapThrowExt};
+use HistoryEntry::{Compound, Hint, Paint, ToggleBorder, ToggleMarker};
 
 const GENERATE_SOLVED: bool = false;
 
@@ -31,14 +51,498 @@ pub struct GameState {
     /// History of board states
     #[wasm_bindgen(skip)]
     pub history: History,
+    /// The number of hints the player has taken, including undone ones
+    #[wasm_bindgen(skip)]
+    pub hints_used: usize,
+    /// Whether undo can take back a hint
+    #[wasm_bindgen(skip)]
+    pub hint_undo: HintUndo,
+    /// The center each cell has been painted into, walls follow the boundaries between owners
+    #[wasm_bindgen(skip)]
+    pub ownership: Ownership,
+    /// Whether painting a cell or drawing a wall in a galaxy also does so to its
+    /// mirror image around the galaxy's center
+    #[wasm_bindgen(skip)]
+    pub mirror_assist: bool,
+    /// Whether the errors are kept up to date after every move, instead of only
+    /// being shown when the player checks the solution
+    #[wasm_bindgen(skip)]
+    pub live_errors: bool,
+    /// The trials in progress, innermost last
+    #[wasm_bindgen(skip)]
+    pub trials: Vec<Trial>,
+    /// How strictly the moves of the player are checked
+    #[wasm_bindgen(skip)]
+    pub strictness: Strictness,
+    /// The active solving time
+    #[wasm_bindgen(skip)]
+    pub timer: Timer,
+    #[wasm_bindgen(skip)]
+    pub stats: Stats,
+    /// How the puzzle was solved, set once when it's solved
+    #[wasm_bindgen(skip)]
+    pub completion: Option<CompletionSummary>,
+    /// The cells pointed at by the last [HintTier::LocateMistake] hint, until the next move
+    #[wasm_bindgen(skip)]
+    pub mistake_region: BTreeSet<Position>,
+    /// Where keyboard moves happen, not saved with the game
+    #[wasm_bindgen(skip)]
+    pub cursor: Cursor,
 }
 
 #[wasm_bindgen]
 impl GameState {
+    /// Serializes the game into a string that can be stored and later passed to [GameState::import].
+    /// See [SavedGame::encode] for how large it gets.
+    pub fn export(&self) -> String {
+        let mut timer = self.timer.clone();
+        timer.stop();
+        SavedGame {
+            version: SAVE_VERSION,
+            puzzle: Puzzle::new(self.universe.clone(), self.objective.clone()),
+            board: self.board.clone(),
+            history: self.history.clone(),
+            hints_used: self.hints_used,
+            hint_undo: self.hint_undo,
+            ownership: Some(self.ownership.clone()),
+            mirror_assist: self.mirror_assist,
+            live_errors: self.live_errors,
+            trials: self.trials.clone(),
+            strictness: self.strictness,
+            timer,
+            stats: self.stats.clone(),
+            completion: self.completion.clone(),
+            checked: self.error.is_some(),
+        }
+        .encode()
+    }
+
+    #[wasm_bindgen(js_name = import)]
+    pub fn import_js(string: &str) -> Result<GameState, JsError> {
+        GameState::import(string).map_err(|error| {
+            InputError::new(InputErrorCode::InvalidSavedGame, error.to_string()).into()
+        })
+    }
+
+    /// Like [GameState::share_code]. Throws if the board is too large for a share code.
+    #[wasm_bindgen(js_name = share_code)]
+    pub fn share_code_js(&self) -> Result<String, JsError> {
+        self.share_code()
+            .map_err(|error| InputError::new(InputErrorCode::InvalidSize, error.to_string()).into())
+    }
+
+    /// Like [GameState::share_code_with_progress]. Throws if the board is too large for a
+    /// share code.
+    #[wasm_bindgen(js_name = share_code_with_progress)]
+    pub fn share_code_with_progress_js(&self) -> Result<String, JsError> {
+        self.share_code_with_progress()
+            .map_err(|error| InputError::new(InputErrorCode::InvalidSize, error.to_string()).into())
+    }
+
+    /// Returns a text rendering of the board, including the errors if the solution has been checked
+    pub fn render(&self) -> String {
+        self.board.render(&self.objective, self.error.as_ref())
+    }
+
+    /// Returns an svg of the puzzle, for printing or embedding
+    pub fn puzzle_svg(&self) -> String {
+        svg::render_puzzle(
+            self.board.get_width(),
+            self.board.get_height(),
+            &self.objective,
+            &SvgOptions::default(),
+        )
+    }
+
+    /// Returns an svg of the solution, with the galaxies colored
+    pub fn solution_svg(&self) -> String {
+        svg::render_solution(
+            &self.objective,
+            &self.universe,
+            &SvgOptions::default().with_default_palette(),
+        )
+    }
+
+    pub fn get_view(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&StateView::from(self)).unwrap_throw()
+    }
+
+    /// Generates a new game of the size, from 1 to [input_error::MAX_GENERATED_SIZE]
+    #[wasm_bindgen(js_name = generate)]
+    pub fn generate_js(size: usize) -> Result<GameState, JsError> {
+        Ok(GameState::generate(input_error::check_size(size)?))
+    }
+
+    /// Like [GameState::toggle_border]. Throws if the cells aren't adjacent cells of the
+    /// board, and returns why the move was refused, if it was.
+    #[wasm_bindgen(js_name = toggle_border)]
+    pub fn toggle_border_js(
+        &mut self,
+        r1: i32,
+        c1: i32,
+        r2: i32,
+        c2: i32,
+    ) -> Result<Option<MoveRejection>, JsError> {
+        input_rules::check_edge(&self.board, Position::new(r1, c1), Position::new(r2, c2))
+            .map_err(InputError::from)?;
+        Ok(self.toggle_border(r1, c1, r2, c2).err())
+    }
+
+    #[wasm_bindgen(js_name = toggle_marker)]
+    pub fn toggle_marker_js(
+        &mut self,
+        r1: i32,
+        c1: i32,
+        r2: i32,
+        c2: i32,
+    ) -> Result<Option<MoveRejection>, JsError> {
+        input_rules::check_edge(&self.board, Position::new(r1, c1), Position::new(r2, c2))
+            .map_err(InputError::from)?;
+        Ok(self.toggle_marker(r1, c1, r2, c2).err())
+    }
+
+    #[wasm_bindgen(js_name = apply_stroke)]
+    pub fn apply_stroke_js(&mut self, borders: JsValue) -> Result<Option<MoveRejection>, JsError> {
+        let borders: Vec<Border> =
+            serde_wasm_bindgen::from_value(borders).map_err(InputError::from)?;
+        for border in &borders {
+            input_error::check_border(&self.board, border)?;
+        }
+        Ok(self.apply_stroke(borders).err())
+    }
+
+    #[wasm_bindgen(js_name = assign_cell)]
+    pub fn assign_cell_js(
+        &mut self,
+        row: i32,
+        column: i32,
+        center_row: i32,
+        center_column: i32,
+    ) -> Result<Option<MoveRejection>, JsError> {
+        input_error::check_cell(&self.board, row, column)?;
+        input_error::check_center(&self.objective, center_row, center_column)?;
+        Ok(self
+            .assign_cell(row, column, center_row, center_column)
+            .err())
+    }
+
+    #[wasm_bindgen(js_name = clear_cell)]
+    pub fn clear_cell_js(
+        &mut self,
+        row: i32,
+        column: i32,
+    ) -> Result<Option<MoveRejection>, JsError> {
+        input_error::check_cell(&self.board, row, column)?;
+        Ok(self.clear_cell(row, column).err())
+    }
+
+    #[wasm_bindgen(js_name = flood_fill)]
+    pub fn flood_fill_js(
+        &mut self,
+        row: i32,
+        column: i32,
+        center_row: i32,
+        center_column: i32,
+    ) -> Result<Option<MoveRejection>, JsError> {
+        input_error::check_cell(&self.board, row, column)?;
+        input_error::check_center(&self.objective, center_row, center_column)?;
+        Ok(self
+            .flood_fill(row, column, center_row, center_column)
+            .err())
+    }
+
+    /// Like [GameState::toggle_edge_towards], for JS
+    #[wasm_bindgen(js_name = toggle_edge_towards)]
+    pub fn toggle_edge_towards_js(&mut self, direction: Direction) -> Option<MoveRejection> {
+        self.toggle_edge_towards(direction).err()
+    }
+
+    /// Moves the cursor half a step, see [Cursor::step]
+    pub fn move_cursor(&mut self, direction: Direction) -> bool {
+        self.cursor.step(&self.board, direction)
+    }
+
+    /// Moves the cursor a whole cell, see [Cursor::jump]
+    pub fn jump_cursor(&mut self, direction: Direction) -> bool {
+        self.cursor.jump(&self.board, direction)
+    }
+
+    /// Moves the cursor to the next center, or the previous one if not forward
+    pub fn cursor_to_center(&mut self, forward: bool) -> bool {
+        let centers = self.objective.centers.iter().map(|center| center.position);
+        self.cursor.jump_to_center(centers, forward)
+    }
+
+    /// Like [GameState::jump_to], throwing if the node isn't in the history
+    #[wasm_bindgen(js_name = jump_to)]
+    pub fn jump_to_js(&mut self, node: usize) -> Result<bool, JsError> {
+        if !self.history.contains(node) {
+            return Err(InputError::new(
+                InputErrorCode::UnknownHistoryNode,
+                format!("there is no node {node} in the history"),
+            )
+            .into());
+        }
+        Ok(self.jump_to(node))
+    }
+
+    /// Sets how strictly moves are checked, see [Strictness]
+    pub fn set_strictness(&mut self, strictness: Strictness) {
+        self.strictness = strictness;
+    }
+
+    /// Removes every wall the player has drawn, every marker and all paint, as a single
+    /// step in the history. Given walls stay. Returns false if there was nothing to clear.
+    pub fn clear_board(&mut self) -> bool {
+        let mut walls: Vec<Border> = self
+            .board
+            .get_borders()
+            .filter(|border| !self.objective.walls.contains(border))
+            .collect();
+        let mut markers: Vec<Border> = self.board.get_markers().collect();
+        walls.sort();
+        markers.sort();
+        let cells: Vec<CellChange> = self
+            .ownership
+            .get_painted_cells()
+            .map(|(cell, owner)| CellChange {
+                cell,
+                before: Some(owner),
+                after: None,
+            })
+            .collect();
+
+        let mut entries: Vec<HistoryEntry> = walls.into_iter().map(ToggleBorder).collect();
+        entries.extend(markers.into_iter().map(ToggleMarker));
+        if !cells.is_empty() {
+            entries.push(Paint {
+                cells,
+                borders: vec![],
+            });
+        }
+        if entries.is_empty() {
+            return false;
+        }
+        let entry = Compound(entries);
+        self.apply(&entry);
+        self.record(entry);
+        true
+    }
+
+    /// Turns live errors on or off, see [GameState::live_errors]
+    pub fn set_live_errors(&mut self, enabled: bool) {
+        self.live_errors = enabled;
+        self.error = enabled.then(|| self.compute_error());
+    }
+
+    /// Turns the mirror assist on or off, see [GameState::mirror_assist]
+    pub fn set_mirror_assist(&mut self, enabled: bool) {
+        self.mirror_assist = enabled;
+    }
+
+    /// Shows the errors of the board, which counts as a check in the stats
+    pub fn check_solution(&mut self) {
+        let error = self.compute_error();
+        self.stats.checks += 1;
+        self.stats.errors_per_check.push(error.count());
+        self.error = Some(error);
+        self.complete_if_solved();
+    }
+
+    /// Like [GameState::check_solvable], for JS
+    #[wasm_bindgen(js_name = check_solvable)]
+    pub fn check_solvable_js(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.check_solvable()).unwrap_throw()
+    }
+
+    /// Like [GameState::check_progress], for JS
+    #[wasm_bindgen(js_name = check_progress)]
+    pub fn check_progress_js(&mut self, count_missing: bool) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.check_progress(count_missing)).unwrap_throw()
+    }
+
+    /// Like [GameState::reveal_solution], for JS
+    #[wasm_bindgen(js_name = reveal_solution)]
+    pub fn reveal_solution_js(&mut self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.reveal_solution()).unwrap_throw()
+    }
+
+    /// Starts or resumes the timer, at the time in milliseconds. Does nothing once solved
+    /// or given up.
+    pub fn resume_timer(&mut self, now: f64) {
+        if self.completion.is_none() && !self.stats.gave_up {
+            self.timer.resume(now as u64);
+        }
+    }
+
+    /// Pauses the timer, at the time in milliseconds, while the game isn't shown
+    pub fn pause_timer(&mut self, now: f64) {
+        self.timer.pause(now as u64);
+    }
+
+    /// Counts the time up to now, in milliseconds. The host calls it before every action,
+    /// so that solving the puzzle stops the timer at the right time.
+    pub fn tick_timer(&mut self, now: f64) {
+        self.timer.tick(now as u64);
+    }
+
+    /// Returns the summary of how the puzzle was solved, or undefined if it isn't yet
+    pub fn get_completion_summary(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.completion).unwrap_throw()
+    }
+
+    /// Takes back the last move. With [HintUndo::Barrier], hints can't be taken back.
+    pub fn undo(&mut self) {
+        if self.hint_undo == HintUndo::Barrier
+            && self
+                .history
+                .get_current_entry()
+                .is_some_and(|entry| entry.get_hint_change() > 0)
+        {
+            return;
+        }
+        if let Some(entry) = self.history.undo() {
+            let entry = entry.inverse();
+            self.stats.undos += 1;
+            self.apply(&entry);
+            self.refresh_error(&entry);
+        }
+    }
+
+    pub fn redo(&mut self) {
+        if let Some(entry) = self.history.redo() {
+            let entry = entry.clone();
+            self.apply(&entry);
+            self.refresh_error(&entry);
+        }
+    }
+
+    /// Returns the history tree, see [crate::model::history::HistoryView]
+    pub fn get_history_view(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.history.get_view()).unwrap_throw()
+    }
+
+    #[wasm_bindgen(js_name = set_history_policy)]
+    pub fn set_history_policy_js(&mut self, policy: JsValue) -> Result<(), JsError> {
+        self.history
+            .set_policy(serde_wasm_bindgen::from_value(policy).map_err(InputError::from)?);
+        Ok(())
+    }
+
+    /// Starts a trial at the current state. Trials nest: a trial begun during another
+    /// is committed or abandoned first.
+    pub fn begin_trial(&mut self) {
+        self.trials
+            .push(Trial::begin(self.history.get_current(), &self.board));
+    }
+
+    /// Keeps the moves of the innermost trial, which become part of the trial around it,
+    /// if any. Returns false if there is no trial.
+    pub fn commit_trial(&mut self) -> bool {
+        self.trials.pop().is_some()
+    }
+
+    /// Rolls the board back to where the innermost trial began. Its moves stay in the
+    /// history on their own branch. Returns false if there is no trial, or if rolling back
+    /// would take back a hint that undo can't.
+    pub fn abandon_trial(&mut self) -> bool {
+        let Some(trial) = self.trials.last() else {
+            return false;
+        };
+        if !self.jump_to(trial.checkpoint) {
+            return false;
+        }
+        self.trials.pop();
+        true
+    }
+
+    /// Reveals a random wall of the solution, see [HintTier::RevealWall]
+    pub fn take_hint(&mut self) {
+        self.request_hint(HintTier::RevealWall);
+    }
+
+    /// Takes a hint of the tier, and adds its cost to the stats. Returns false, without
+    /// counting the hint, if there's nothing to show: no wrong wall to locate, or no wall
+    /// left to reveal.
+    pub fn request_hint(&mut self, tier: HintTier) -> bool {
+        let taken = match tier {
+            HintTier::LocateMistake => self.locate_mistake(),
+            HintTier::RevealWall => self.reveal_wall(),
+            HintTier::RevealGalaxy => self.reveal_galaxy(),
+        };
+        if taken {
+            self.hints_used += 1;
+            self.stats.hint_cost += tier.cost();
+            match tier {
+                HintTier::LocateMistake => self.stats.mistakes_located += 1,
+                HintTier::RevealWall => self.stats.walls_revealed += 1,
+                HintTier::RevealGalaxy => self.stats.galaxies_revealed += 1,
+            }
+        }
+        taken
+    }
+
+    #[wasm_bindgen(js_name = set_hint_undo)]
+    pub fn set_hint_undo_js(&mut self, hint_undo: JsValue) -> Result<(), JsError> {
+        self.hint_undo = serde_wasm_bindgen::from_value(hint_undo).map_err(InputError::from)?;
+        Ok(())
+    }
+}
+
+impl GameState {
+    /// Returns a short, url-safe code for sharing the puzzle, without the solution
+    pub fn share_code(&self) -> Result<String, ShareCodeError> {
+        SharedPuzzle::new(
+            self.board.get_width(),
+            self.board.get_height(),
+            self.objective.clone(),
+        )
+        .encode()
+    }
+
+    /// Like [GameState::share_code], including the walls the player has drawn
+    pub fn share_code_with_progress(&self) -> Result<String, ShareCodeError> {
+        SharedPuzzle::with_progress(
+            self.board.get_width(),
+            self.board.get_height(),
+            self.objective.clone(),
+            &self.board,
+        )
+        .encode()
+    }
+
+    /// Compares the walls drawn so far with the solution, optionally counting the walls
+    /// still missing. Unlike [GameState::check_solution], the board needn't be finished.
+    pub fn check_progress(&mut self, count_missing: bool) -> ProgressReport {
+        self.stats.progress_checks += 1;
+        ProgressReport::compare(&self.board, &self.objective, &self.universe, count_missing)
+    }
+
+    /// Checks whether the walls drawn so far can still lead to any solution, using the
+    /// solver instead of the stored solution, which imported puzzles may not match
+    pub fn check_solvable(&self) -> Solvability {
+        Solver::check_board(&self.board, &self.objective)
+    }
+
+    /// Compares the player's walls with the solution, leaving the board as it is. Unless
+    /// the puzzle is already solved, the game is marked as given up and the timer stops.
+    pub fn reveal_solution(&mut self) -> SolutionDiff {
+        if self.completion.is_none() {
+            self.stats.gave_up = true;
+            self.timer.stop();
+        }
+        SolutionDiff::compare(&self.board, &self.objective, &self.universe)
+    }
+
     pub fn generate(size: usize) -> GameState {
-        let universe = Universe::generate(size, size);
+        GameState::new(Universe::generate(size, size))
+    }
+
+    /// Starts a game on an empty board, with the universe as the solution
+    pub fn new(universe: Universe) -> GameState {
+        let (width, height) = (universe.get_width(), universe.get_height());
         let objective = Objective::generate(&universe);
-        let mut board = Board::new(size, size);
+        let mut board = Board::new(width, height);
         let error = None;
         let history = History::new();
 
@@ -58,83 +562,599 @@ impl GameState {
             objective,
             error,
             history,
+            hints_used: 0,
+            hint_undo: HintUndo::default(),
+            ownership: Ownership::new(width, height),
+            mirror_assist: false,
+            live_errors: false,
+            trials: Vec::new(),
+            strictness: Strictness::default(),
+            timer: Timer::default(),
+            stats: Stats::default(),
+            completion: None,
+            mistake_region: BTreeSet::new(),
+            cursor: Cursor::default(),
         }
     }
 
-    pub fn get_view(&self) -> JsValue {
-        serde_wasm_bindgen::to_value(&StateView::from(self)).unwrap_throw()
+    /// Moves the board to the state of the node in the history, on any branch.
+    /// Returns false and does nothing if there is no such node.
+    /// With [HintUndo::Barrier], jumps that would take back a hint are refused as well.
+    pub fn jump_to(&mut self, node: usize) -> bool {
+        let Some(steps) = self.history.get_path(node) else {
+            return false;
+        };
+        if self.hint_undo == HintUndo::Barrier
+            && steps.iter().any(|step| step.get_hint_change() < 0)
+        {
+            return false;
+        }
+        self.history.jump_to(node);
+        let entry = Compound(steps);
+        self.apply(&entry);
+        self.refresh_error(&entry);
+        true
     }
 
-    pub fn toggle_border(&mut self, r1: i32, c1: i32, r2: i32, c2: i32) {
+    /// Resumes a game serialized by [GameState::export]
+    pub fn import(string: &str) -> Result<GameState, SaveError> {
+        SavedGame::decode(string).map(GameState::from)
+    }
+
+    /// Toggles the edge the cursor points at in the direction, see [Cursor::get_edge], like
+    /// [GameState::toggle_border]
+    pub fn toggle_edge_towards(&mut self, direction: Direction) -> Result<(), MoveRejection> {
+        let edge = self
+            .cursor
+            .get_edge(&self.board, direction)
+            .ok_or(MoveRejection::OutsideBoard)?;
+        let (p1, p2) = edge.into();
+        self.toggle_border(p1.row, p1.column, p2.row, p2.column)
+    }
+
+    /// Toggles the wall between the two cells. Locked walls can't be toggled, and
+    /// depending on the [Strictness], neither can walls that are certainly wrong. With the
+    /// mirror assist, a wall in a painted galaxy is mirrored around its center if the mirror
+    /// image may be toggled too, and the move is refused if it's outside the board.
+    pub fn toggle_border(
+        &mut self,
+        r1: i32,
+        c1: i32,
+        r2: i32,
+        c2: i32,
+    ) -> Result<(), MoveRejection> {
         let p1 = Position::new(r1, c1);
         let p2 = Position::new(r2, c2);
-        let border = Border::new(p1, p2);
+        let border = input_rules::check_edge(&self.board, p1, p2)?;
+        input_rules::check_wall(&self.board, &self.objective, &border, self.strictness)?;
+        let owner = self
+            .ownership
+            .get_owner(&p1)
+            .or_else(|| self.ownership.get_owner(&p2));
+        if let (true, Some(center)) = (self.mirror_assist, owner) {
+            let mirror = Border::new(center.mirror_position(&p1), center.mirror_position(&p2));
+            if !self.board.contains_border(&mirror) {
+                return Err(MoveRejection::MirrorOutsideBoard);
+            }
+            if mirror != border {
+                let wall = !self.board.is_active(&border);
+                let mut borders = vec![border];
+                if self.board.is_active(&mirror) != wall
+                    && input_rules::check_wall(
+                        &self.board,
+                        &self.objective,
+                        &mirror,
+                        self.strictness,
+                    )
+                    .is_ok()
+                {
+                    borders.push(mirror);
+                }
+                for border in &borders {
+                    self.board.toggle_wall(border.p1(), border.p2());
+                }
+                self.record(Compound(borders.into_iter().map(ToggleBorder).collect()));
+                return Ok(());
+            }
+        }
         self.board.toggle_wall(p1, p2);
-        self.history.push(ToggleBorder(border));
-        self.error = None;
+        self.record(ToggleBorder(border));
+        Ok(())
     }
 
-    pub fn check_solution(&mut self) {
-        self.error = self.board.compute_error(&self.objective).into();
+    /// Marks the edge between the two cells as definitely not a wall, or removes the marker.
+    /// Edges with a wall can't be marked.
+    pub fn toggle_marker(
+        &mut self,
+        r1: i32,
+        c1: i32,
+        r2: i32,
+        c2: i32,
+    ) -> Result<(), MoveRejection> {
+        let border =
+            input_rules::check_edge(&self.board, Position::new(r1, c1), Position::new(r2, c2))?;
+        if self.board.is_active(&border) {
+            return Err(MoveRejection::MarkerOnWall);
+        }
+        self.board.toggle_marker(&border);
+        self.record(ToggleMarker(border));
+        Ok(())
     }
 
-    pub fn undo(&mut self) {
-        if let Some(entry) = self.history.undo() {
-            match entry {
-                ToggleBorder(border) => self.board.toggle_wall(border.p1(), border.p2()),
-            };
-            self.error = None;
+    /// Draws or erases walls along the edges of a drag, as a single step in the history.
+    /// The first edge that may be toggled decides: if it has a wall, the stroke erases
+    /// walls, otherwise it draws them. Edges that may not be toggled are skipped, and the
+    /// mirror assist doesn't apply.
+    pub fn apply_stroke(&mut self, borders: Vec<Border>) -> Result<(), MoveRejection> {
+        let mut borders = borders
+            .into_iter()
+            .filter(|border| {
+                input_rules::check_wall(&self.board, &self.objective, border, self.strictness)
+                    .is_ok()
+            })
+            .peekable();
+        let Some(first) = borders.peek() else {
+            return Err(MoveRejection::NoChange);
+        };
+        let draw = !self.board.is_active(first);
+        let toggled: Vec<Border> = borders
+            .filter(|border| self.board.is_active(border) != draw)
+            .unique()
+            .collect();
+        if toggled.is_empty() {
+            return Err(MoveRejection::NoChange);
         }
+        let entry = Compound(toggled.into_iter().map(ToggleBorder).collect());
+        self.apply(&entry);
+        self.record(entry);
+        Ok(())
     }
 
-    pub fn redo(&mut self) {
-        if let Some(entry) = self.history.redo() {
-            match entry {
-                ToggleBorder(border) => self.board.toggle_wall(border.p1(), border.p2()),
-            };
-            self.error = None;
+    /// Paints the cell into the galaxy of the given center, given in half-steps.
+    /// With the mirror assist, the mirrored cell is painted too, and the move is
+    /// refused if it's outside the board.
+    pub fn assign_cell(
+        &mut self,
+        row: i32,
+        column: i32,
+        center_row: i32,
+        center_column: i32,
+    ) -> Result<(), MoveRejection> {
+        let cell = Position::new(row, column);
+        let center = Position::new(center_row, center_column);
+        self.check_paint(&cell, &center)?;
+        let cells = self
+            .with_mirrors([cell], &center)
+            .ok_or(MoveRejection::MirrorOutsideBoard)?;
+        self.paint(cells, Some(center))
+    }
+
+    /// Removes the paint from the cell. With the mirror assist, the mirrored cell is
+    /// cleared too if it's painted into the same center.
+    pub fn clear_cell(&mut self, row: i32, column: i32) -> Result<(), MoveRejection> {
+        let cell = Position::new(row, column);
+        if !self.board.contains(&cell) {
+            return Err(MoveRejection::OutsideBoard);
         }
+        let mut cells = vec![cell];
+        if let (true, Some(center)) = (self.mirror_assist, self.ownership.get_owner(&cell)) {
+            let mirror = center.mirror_position(&cell);
+            if self.ownership.get_owner(&mirror) == Some(center) {
+                cells.push(mirror);
+            }
+        }
+        self.paint(cells, None)
     }
 
-    pub fn take_hint(&mut self) {
-        let border = self
-            .universe
-            .get_galaxies()
+    /// Paints the cell into the galaxy of the given center, along with every cell connected
+    /// to it that has the same owner and isn't separated from it by a wall. With the mirror
+    /// assist, the mirror image of the region is painted too.
+    pub fn flood_fill(
+        &mut self,
+        row: i32,
+        column: i32,
+        center_row: i32,
+        center_column: i32,
+    ) -> Result<(), MoveRejection> {
+        let cell = Position::new(row, column);
+        let center = Position::new(center_row, center_column);
+        self.check_paint(&cell, &center)?;
+        let region = self.ownership.get_fill_region(&self.board, &cell);
+        let cells = self
+            .with_mirrors(region, &center)
+            .ok_or(MoveRejection::MirrorOutsideBoard)?;
+        self.paint(cells, Some(center))
+    }
+
+    /// Checks that the cell is on the board and the center is one of the puzzle's
+    fn check_paint(&self, cell: &Position, center: &Position) -> Result<(), MoveRejection> {
+        if !self.board.contains(cell) {
+            return Err(MoveRejection::OutsideBoard);
+        }
+        if !self.is_center(center) {
+            return Err(MoveRejection::NotACenter);
+        }
+        Ok(())
+    }
+
+    /// Points at the region of the board with the first wrong wall
+    fn locate_mistake(&mut self) -> bool {
+        let solution_walls = hints::get_solution_walls(&self.universe, &self.board);
+        let Some(mistake) = hints::find_mistake(&self.board, &solution_walls) else {
+            return false;
+        };
+        let regions = self.board.get_regions([mistake.p1(), mistake.p2()]);
+        self.mistake_region = regions
             .iter()
-            .flat_map(|g| g.get_borders())
-            .filter(|border| self.board.contains(&border.p1()) && self.board.contains(&border.p2()))
+            .flat_map(|region| region.get_positions())
+            .copied()
+            .collect();
+        true
+    }
+
+    /// Reveals and locks a random wall of the solution that isn't on the board yet
+    fn reveal_wall(&mut self) -> bool {
+        let border = hints::get_solution_walls(&self.universe, &self.board)
+            .into_iter()
             .filter(|border| !self.objective.walls.contains(border))
             .filter(|border| !self.board.is_active(border))
             .choose(&mut rand::thread_rng());
+        let Some(border) = border else {
+            return false;
+        };
+        let entry = Hint {
+            wall: border,
+            taken: true,
+        };
+        self.apply(&entry);
+        self.record(entry);
+        true
+    }
+
+    /// Reveals and locks the walls of a galaxy of the solution that aren't on the board, as a
+    /// single step in the history. Like [GameState::reveal_wall], walls the player drew are
+    /// left alone, so that taking the hint back doesn't remove them. The galaxy is the one
+    /// with the first wrong wall, unless it has nothing left to reveal, and otherwise the
+    /// first one that has.
+    fn reveal_galaxy(&mut self) -> bool {
+        let solution_walls = hints::get_solution_walls(&self.universe, &self.board);
+        let unlocked: Vec<Border> = solution_walls
+            .iter()
+            .filter(|wall| !self.objective.walls.contains(wall))
+            .filter(|wall| !self.board.is_active(wall))
+            .copied()
+            .collect();
+        let galaxy_walls = |cell: Position| -> BTreeSet<Border> {
+            self.universe
+                .get_galaxy(&cell)
+                .get_borders()
+                .into_iter()
+                .filter(|border| unlocked.contains(border))
+                .collect()
+        };
+        let mut walls = hints::find_mistake(&self.board, &solution_walls)
+            .map(|mistake| galaxy_walls(mistake.p1()))
+            .unwrap_or_default();
+        if walls.is_empty() {
+            match unlocked.first() {
+                Some(wall) => walls = galaxy_walls(wall.p1()),
+                None => return false,
+            }
+        }
+        let entry = Compound(
+            walls
+                .into_iter()
+                .map(|wall| Hint { wall, taken: true })
+                .collect(),
+        );
+        self.apply(&entry);
+        self.record(entry);
+        true
+    }
+
+    /// Returns true iff the wall is given or revealed by a hint, which the player can't change
+    fn is_locked(&self, border: &Border) -> bool {
+        self.objective.walls.contains(border)
+    }
+
+    fn is_center(&self, position: &Position) -> bool {
+        self.objective
+            .centers
+            .iter()
+            .any(|gc| gc.position == *position)
+    }
+
+    /// Returns the errors of the board, including the painted cells that are outside their galaxy
+    fn compute_error(&self) -> BoardError {
+        BoardError {
+            misassigned_cells: self.ownership.get_misassigned_cells(&self.board),
+            ..self.board.compute_error(&self.objective)
+        }
+    }
+
+    /// Returns the cells along with their mirror images around the center if the mirror
+    /// assist is on, or None if any mirror image is outside the board
+    fn with_mirrors(
+        &self,
+        cells: impl IntoIterator<Item = Position>,
+        center: &Position,
+    ) -> Option<BTreeSet<Position>> {
+        let mut with_mirrors = BTreeSet::new();
+        for cell in cells {
+            with_mirrors.insert(cell);
+            if self.mirror_assist {
+                let mirror = center.mirror_position(&cell);
+                if !self.board.contains(&mirror) {
+                    return None;
+                }
+                with_mirrors.insert(mirror);
+            }
+        }
+        Some(with_mirrors)
+    }
+
+    /// Paints the cells into the owner, or clears them if the owner is None, and redraws
+    /// the walls around them to follow the new boundaries, as a single step in the history.
+    /// Refuses the move if no cell changes, or if a wall it needs is refused by
+    /// [input_rules::check_wall], such as one through a center in [Strictness::Strict].
+    fn paint(
+        &mut self,
+        cells: impl IntoIterator<Item = Position>,
+        owner: Option<Position>,
+    ) -> Result<(), MoveRejection> {
+        let cells: Vec<CellChange> = cells
+            .into_iter()
+            .filter(|cell| self.ownership.get_owner(cell) != owner)
+            .map(|cell| CellChange {
+                cell,
+                before: self.ownership.get_owner(&cell),
+                after: owner,
+            })
+            .collect();
+        if cells.is_empty() {
+            return Err(MoveRejection::NoChange);
+        }
+        for change in &cells {
+            self.ownership.set_owner(&change.cell, change.after);
+        }
+
+        let borders: BTreeSet<Border> = cells
+            .iter()
+            .flat_map(|change| {
+                change
+                    .cell
+                    .adjacent()
+                    .into_iter()
+                    .map(move |neighbour| Border::new(change.cell, neighbour))
+            })
+            .filter(|border| self.board.contains_border(border))
+            .filter(|border| self.board.is_active(border) != self.ownership.is_boundary(border))
+            .filter(|border| !self.is_locked(border))
+            .collect();
+        let refusal = borders.iter().find_map(|border| {
+            input_rules::check_wall(&self.board, &self.objective, border, self.strictness).err()
+        });
+        if let Some(rejection) = refusal {
+            for change in &cells {
+                self.ownership.set_owner(&change.cell, change.before);
+            }
+            return Err(rejection);
+        }
+        for border in &borders {
+            self.board.toggle_wall(border.p1(), border.p2());
+        }
+
+        self.record(Paint {
+            cells,
+            borders: borders.into_iter().collect(),
+        });
+        Ok(())
+    }
+
+    /// Adds a move that has been made to the history
+    fn record(&mut self, entry: HistoryEntry) {
+        if entry.get_hint_change() == 0 {
+            self.stats.moves += 1;
+        }
+        self.refresh_error(&entry);
+        self.history.push(entry);
+    }
+
+    /// Updates the errors after the entry has been applied. In live mode only the parts of
+    /// the board touched by the entry are checked again, otherwise the errors are hidden
+    /// until the player checks the solution.
+    fn refresh_error(&mut self, entry: &HistoryEntry) {
+        self.mistake_region.clear();
+        self.refresh_live_error(entry);
+        self.complete_if_solved();
+    }
+
+    /// Produces the completion summary the first time the board is solved
+    fn complete_if_solved(&mut self) {
+        let solved = self.error.as_ref().is_some_and(BoardError::is_error_free);
+        if !solved || self.completion.is_some() {
+            return;
+        }
+        self.timer.stop();
+        self.completion = Some(CompletionSummary {
+            width: self.board.get_width(),
+            height: self.board.get_height(),
+            difficulty: Difficulty::estimate(&self.universe, &self.objective),
+            elapsed_ms: self.timer.get_elapsed_ms(),
+            hints_used: self.hints_used,
+            stats: self.stats.clone(),
+        });
+    }
 
-        if let Some(border) = border {
-            self.board.add_wall(border.p1(), border.p2());
-            self.objective.walls.insert(border);
+    fn refresh_live_error(&mut self, entry: &HistoryEntry) {
+        if !self.live_errors {
             self.error = None;
+            return;
+        }
+        let Some(error) = &mut self.error else {
+            self.error = Some(self.compute_error());
+            return;
+        };
+        let borders = entry.get_borders();
+        self.board.update_error(error, &self.objective, &borders);
+
+        let cells = borders
+            .iter()
+            .flat_map(|border| [border.p1(), border.p2()])
+            .chain(entry.get_cells());
+        let galaxies = self.board.get_regions(cells);
+        let affected: HashSet<Position> = galaxies
+            .iter()
+            .flat_map(|galaxy| galaxy.get_positions())
+            .copied()
+            .collect();
+        error
+            .misassigned_cells
+            .retain(|cell| !affected.contains(cell));
+        error
+            .misassigned_cells
+            .extend(self.ownership.get_misassigned_cells_in(&galaxies));
+    }
+
+    /// Applies the entry to the board and the ownership
+    fn apply(&mut self, entry: &HistoryEntry) {
+        match entry {
+            ToggleBorder(border) => {
+                self.board.toggle_wall(border.p1(), border.p2());
+            }
+            ToggleMarker(border) => {
+                self.board.toggle_marker(border);
+            }
+            Compound(entries) => {
+                for entry in entries {
+                    self.apply(entry);
+                }
+            }
+            Hint { wall, taken: true } => {
+                self.board.add_wall(wall.p1(), wall.p2());
+                self.objective.walls.insert(*wall);
+            }
+            Hint { wall, taken: false } => {
+                self.board.remove_wall(wall.p1(), wall.p2());
+                self.objective.walls.remove(wall);
+            }
+            Paint { cells, borders } => {
+                for change in cells {
+                    self.ownership.set_owner(&change.cell, change.after);
+                }
+                for border in borders {
+                    self.board.toggle_wall(border.p1(), border.p2());
+                }
+            }
         }
     }
 }
 
+impl From<SavedGame> for GameState {
+    fn from(saved_game: SavedGame) -> Self {
+        let Puzzle {
+            objective,
+            solution,
+            ..
+        } = saved_game.puzzle;
+        let ownership = saved_game.ownership.unwrap_or_else(|| {
+            Ownership::new(saved_game.board.get_width(), saved_game.board.get_height())
+        });
+        let mut state = GameState {
+            universe: solution,
+            board: saved_game.board,
+            objective,
+            error: None,
+            history: saved_game.history,
+            hints_used: saved_game.hints_used,
+            hint_undo: saved_game.hint_undo,
+            ownership,
+            mirror_assist: saved_game.mirror_assist,
+            live_errors: saved_game.live_errors,
+            trials: saved_game.trials,
+            strictness: saved_game.strictness,
+            timer: saved_game.timer,
+            stats: saved_game.stats,
+            completion: saved_game.completion,
+            mistake_region: BTreeSet::new(),
+            cursor: Cursor::default(),
+        };
+        if saved_game.checked || saved_game.live_errors {
+            state.error = Some(state.compute_error());
+        }
+        state
+    }
+}
+
 /// The parts of the state necessary for rendering
-#[derive(Serialize, TS)]
+#[derive(Serialize, Deserialize, TS)]
 #[ts(export)]
 pub struct StateView {
     pub vertical_borders: Vec<Vec<bool>>,
     pub horizontal_borders: Vec<Vec<bool>>,
+    /// Edges marked as definitely not a wall, in the same layout as the borders
+    pub vertical_markers: Vec<Vec<bool>>,
+    pub horizontal_markers: Vec<Vec<bool>>,
     pub objective: Objective,
+    /// The center each cell is painted into, in half-steps, by row
+    pub owners: Vec<Vec<Option<Position>>>,
     pub error: Option<BoardError>,
+    /// The walls drawn since the outermost trial began, in the same layout as the borders
+    pub vertical_trial_walls: Vec<Vec<bool>>,
+    pub horizontal_trial_walls: Vec<Vec<bool>>,
+    /// The number of nested trials in progress
+    pub trial_depth: usize,
+    /// The number of hints taken, including undone ones
+    pub hints_used: usize,
+    /// The number of hint walls on the board, which are part of the objective's walls
+    pub hints_on_board: usize,
     pub has_future: bool,
     pub has_past: bool,
     pub is_solved: bool,
+    /// The active solving time up to the last timestamp, in milliseconds
+    #[ts(type = "number")]
+    pub elapsed_ms: u64,
+    pub timer_running: bool,
+    pub stats: Stats,
+    pub completion: Option<CompletionSummary>,
+    /// The hint tiers with their costs
+    pub hint_tiers: Vec<HintTierView>,
+    /// The cells pointed at by the last hint locating a mistake, until the next move
+    pub mistake_region: Vec<Position>,
+    /// Once the player gave up, how the walls on the board compare with the solution
+    pub solution_diff: Option<SolutionDiff>,
+    /// The keyboard cursor in half-steps, on a cell, an edge or a center between cells
+    pub cursor: Position,
+    /// The regions enclosed by walls, and which of them are finished galaxies
+    pub regions: RegionMap,
 }
 
 impl From<&GameState> for StateView {
     fn from(state: &GameState) -> Self {
+        let trial_walls = state
+            .trials
+            .first()
+            .map(|trial| trial.get_new_walls(&state.board))
+            .unwrap_or_default();
         StateView {
             vertical_borders: state.board.get_vertical_borders(),
             horizontal_borders: state.board.get_horizontal_borders(),
+            vertical_markers: state.board.get_vertical_markers(),
+            horizontal_markers: state.board.get_horizontal_markers(),
             objective: state.objective.clone(),
+            owners: state.ownership.get_owners(),
             error: state.error.clone(),
+            vertical_trial_walls: state.board.get_vertical_matrix(trial_walls.iter().copied()),
+            horizontal_trial_walls: state.board.get_horizontal_matrix(trial_walls),
+            trial_depth: state.trials.len(),
+            hints_used: state.hints_used,
+            hints_on_board: state
+                .history
+                .get_past()
+                .into_iter()
+                .map(HistoryEntry::get_hint_change)
+                .sum::<isize>()
+                .max(0) as usize,
             has_future: state.history.has_future(),
             has_past: state.history.has_past(),
             is_solved: state
@@ -142,16 +1162,846 @@ impl From<&GameState> for StateView {
                 .as_ref()
                 .map(|it| it.is_error_free())
                 .unwrap_or(false),
+            elapsed_ms: state.timer.get_elapsed_ms(),
+            timer_running: state.timer.is_running(),
+            stats: state.stats.clone(),
+            completion: state.completion.clone(),
+            hint_tiers: HintTierView::all(&state.stats),
+            mistake_region: state.mistake_region.iter().copied().collect(),
+            solution_diff: state
+                .stats
+                .gave_up
+                .then(|| SolutionDiff::compare(&state.board, &state.objective, &state.universe)),
+            cursor: state.cursor.get_position(),
+            regions: RegionMap::new(&state.board, &state.objective),
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::model::game_state::GameState;
+    use crate::model::border::Border;
+    use crate::model::cursor::Direction;
+    use crate::model::fixtures;
+    use crate::model::galaxy::Galaxy;
+    use crate::model::game_state::{GameState, StateView};
+    use crate::model::hints::HintTier;
+    use crate::model::history::{HintUndo, HistoryEntry::Compound, HistoryEntry::Hint};
+    use crate::model::input_rules::{MoveRejection, Strictness};
+    use crate::model::ownership::Ownership;
+    use crate::model::position::Position;
+    use crate::model::solver::Solvability;
+    use crate::model::universe::Universe;
+    use std::collections::HashSet;
+
+    /// A game on [fixtures::small_universe]
+    fn small_state() -> GameState {
+        GameState::new(fixtures::small_universe())
+    }
+
+    fn walls(state: &GameState) -> Vec<(Position, Position)> {
+        let mut walls: Vec<_> = state
+            .board
+            .get_borders()
+            .map(|border| (border.p1(), border.p2()))
+            .collect();
+        walls.sort();
+        walls
+    }
 
     #[test]
     fn should_generate_state() {
         GameState::generate(10);
     }
+
+    #[test]
+    fn painting_should_draw_walls_along_boundaries() {
+        let mut state = small_state();
+        state.assign_cell(0, 2, 0, 4).unwrap();
+        assert_eq!(
+            state.ownership.get_owner(&Position::new(0, 2)),
+            Some(Position::new(0, 4))
+        );
+        assert_eq!(
+            walls(&state),
+            vec![
+                (Position::new(0, 1), Position::new(0, 2)),
+                (Position::new(0, 2), Position::new(1, 2)),
+            ]
+        );
+
+        state.assign_cell(1, 2, 2, 4).unwrap();
+        assert_eq!(walls(&state).len(), 3);
+
+        state.clear_cell(0, 2).unwrap();
+        assert_eq!(
+            walls(&state),
+            vec![
+                (Position::new(0, 2), Position::new(1, 2)),
+                (Position::new(1, 1), Position::new(1, 2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn painting_should_ignore_unknown_centers_and_cells() {
+        let mut state = small_state();
+        assert_eq!(
+            state.assign_cell(0, 0, 0, 0),
+            Err(MoveRejection::NotACenter)
+        );
+        assert_eq!(
+            state.assign_cell(5, 0, 1, 1),
+            Err(MoveRejection::OutsideBoard)
+        );
+        assert_eq!(state.clear_cell(0, 0), Err(MoveRejection::NoChange));
+        assert!(!state.history.has_past());
+        assert_eq!(state.ownership, Ownership::new(3, 2));
+    }
+
+    #[test]
+    fn undo_and_redo_should_restore_paint_and_walls() {
+        let mut state = small_state();
+        state.toggle_border(0, 0, 1, 0).unwrap();
+        state.assign_cell(0, 2, 0, 4).unwrap();
+        let painted_walls = walls(&state);
+
+        state.undo();
+        assert_eq!(state.ownership.get_owner(&Position::new(0, 2)), None);
+        assert_eq!(
+            walls(&state),
+            vec![(Position::new(0, 0), Position::new(1, 0))]
+        );
+
+        state.redo();
+        assert_eq!(walls(&state), painted_walls);
+        assert_eq!(
+            state.ownership.get_owner(&Position::new(0, 2)),
+            Some(Position::new(0, 4))
+        );
+    }
+
+    #[test]
+    fn flood_fill_should_be_a_single_step() {
+        let mut state = small_state();
+        state.toggle_border(0, 1, 0, 2).unwrap();
+        state.toggle_border(1, 1, 1, 2).unwrap();
+        state.flood_fill(1, 0, 1, 1).unwrap();
+
+        for cell in [(0, 0), (0, 1), (1, 0), (1, 1)] {
+            assert_eq!(
+                state.ownership.get_owner(&Position::from(cell)),
+                Some(Position::new(1, 1))
+            );
+        }
+        assert_eq!(state.ownership.get_owner(&Position::new(0, 2)), None);
+
+        state.undo();
+        assert_eq!(state.ownership, Ownership::new(3, 2));
+        assert_eq!(walls(&state).len(), 2);
+    }
+
+    #[test]
+    fn check_should_report_misassigned_cells() {
+        let mut state = small_state();
+        state.assign_cell(0, 0, 1, 1).unwrap();
+        state.assign_cell(0, 1, 1, 1).unwrap();
+        state.assign_cell(1, 0, 1, 1).unwrap();
+        state.assign_cell(1, 1, 1, 1).unwrap();
+        state.assign_cell(0, 2, 0, 4).unwrap();
+        state.assign_cell(1, 2, 1, 1).unwrap();
+        // Walls the cell off from its center
+        state.toggle_border(1, 1, 1, 2).unwrap();
+
+        state.check_solution();
+        let error = state.error.clone().unwrap();
+        assert_eq!(
+            error.misassigned_cells,
+            [Position::new(1, 2)].into_iter().collect()
+        );
+        assert!(!error.is_error_free());
+
+        state.assign_cell(1, 2, 2, 4).unwrap();
+        state.check_solution();
+        assert!(state.error.unwrap().is_error_free());
+    }
+
+    #[test]
+    fn paint_should_survive_export() {
+        let mut state = small_state();
+        state.assign_cell(0, 2, 0, 4).unwrap();
+        let mut resumed = GameState::import(&state.export()).unwrap();
+        assert_eq!(resumed.ownership, state.ownership);
+        resumed.undo();
+        assert_eq!(resumed.ownership, Ownership::new(3, 2));
+        assert!(walls(&resumed).is_empty());
+    }
+
+    #[test]
+    fn mirror_assist_should_paint_the_mirrored_cell_in_one_step() {
+        let mut state = small_state();
+        state.set_mirror_assist(true);
+        assert!(state.assign_cell(0, 0, 1, 1).is_ok());
+        assert_eq!(
+            state.ownership.get_owner(&Position::new(1, 1)),
+            Some(Position::new(1, 1))
+        );
+
+        state.undo();
+        assert_eq!(state.ownership, Ownership::new(3, 2));
+        assert!(!state.history.has_past());
+    }
+
+    #[test]
+    fn mirror_assist_should_refuse_mirrors_outside_the_board() {
+        let mut state = small_state();
+        state.set_mirror_assist(true);
+        assert!(state.assign_cell(0, 2, 1, 1).is_err());
+        assert!(state.flood_fill(0, 0, 1, 1).is_err());
+        assert_eq!(state.ownership, Ownership::new(3, 2));
+        assert!(!state.history.has_past());
+
+        state.set_mirror_assist(false);
+        assert!(state.assign_cell(0, 2, 1, 1).is_ok());
+    }
+
+    #[test]
+    fn mirror_assist_should_mirror_walls_in_painted_galaxies() {
+        let mut state = small_state();
+        for (row, column) in [(0, 0), (0, 1), (1, 0), (1, 1)] {
+            state.assign_cell(row, column, 1, 1).unwrap();
+        }
+        state.set_mirror_assist(true);
+
+        assert!(state.toggle_border(0, 0, 0, 1).is_ok());
+        assert!(state
+            .board
+            .is_wall(Position::new(0, 0), Position::new(0, 1)));
+        assert!(state
+            .board
+            .is_wall(Position::new(1, 0), Position::new(1, 1)));
+
+        state.undo();
+        assert!(!state
+            .board
+            .is_wall(Position::new(0, 0), Position::new(0, 1)));
+        assert!(!state
+            .board
+            .is_wall(Position::new(1, 0), Position::new(1, 1)));
+
+        // The mirror of the galaxy's outer wall is outside the board
+        assert!(state.toggle_border(1, 1, 1, 2).is_err());
+    }
+
+    #[test]
+    fn mirror_assist_should_survive_export() {
+        let mut state = small_state();
+        state.set_mirror_assist(true);
+        assert!(GameState::import(&state.export()).unwrap().mirror_assist);
+    }
+
+    #[test]
+    fn live_errors_should_follow_every_move() {
+        let mut state = small_state();
+        state.set_live_errors(true);
+        assert!(state.error.is_some());
+
+        state.toggle_border(0, 1, 0, 2).unwrap();
+        state.assign_cell(0, 2, 0, 4).unwrap();
+        state.toggle_border(0, 0, 1, 0).unwrap();
+        state.undo();
+        state.redo();
+        state.undo();
+        state.clear_cell(0, 2).unwrap();
+        state.take_hint();
+        assert_eq!(state.error, Some(state.compute_error()));
+
+        state.set_live_errors(false);
+        assert!(state.error.is_none());
+    }
+
+    #[test]
+    fn live_errors_should_mark_the_board_solved() {
+        let mut state = small_state();
+        state.set_live_errors(true);
+        state.toggle_border(0, 1, 0, 2).unwrap();
+        state.toggle_border(1, 1, 1, 2).unwrap();
+        assert!(!StateView::from(&state).is_solved);
+
+        state.toggle_border(0, 2, 1, 2).unwrap();
+        assert!(StateView::from(&state).is_solved);
+    }
+
+    #[test]
+    fn markers_should_be_undoable_and_hidden_under_walls() {
+        let mut state = small_state();
+        assert!(state.toggle_marker(0, 0, 0, 1).is_ok());
+        assert!(state.toggle_marker(0, 0, 1, 1).is_err());
+        assert!(state.toggle_marker(0, 2, 0, 3).is_err());
+        assert_eq!(
+            StateView::from(&state).vertical_markers,
+            vec![vec![true, false], vec![false, false]]
+        );
+
+        state.toggle_border(0, 0, 0, 1).unwrap();
+        assert!(state.toggle_marker(0, 0, 0, 1).is_err());
+        assert_eq!(
+            StateView::from(&state).vertical_markers,
+            vec![vec![false, false], vec![false, false]]
+        );
+
+        state.undo();
+        state.undo();
+        assert!(!state.history.has_past());
+        assert_eq!(
+            StateView::from(&state).vertical_markers,
+            vec![vec![false, false], vec![false, false]]
+        );
+    }
+
+    #[test]
+    fn markers_should_not_be_errors() {
+        let mut state = small_state();
+        state.toggle_border(0, 1, 0, 2).unwrap();
+        state.toggle_border(1, 1, 1, 2).unwrap();
+        state.toggle_border(0, 2, 1, 2).unwrap();
+        state.toggle_marker(0, 0, 0, 1).unwrap();
+        state.toggle_marker(0, 0, 1, 0).unwrap();
+        state.check_solution();
+        assert!(state.error.unwrap().is_error_free());
+    }
+
+    #[test]
+    fn markers_should_survive_export() {
+        let mut state = small_state();
+        state.toggle_marker(1, 0, 1, 1).unwrap();
+        let resumed = GameState::import(&state.export()).unwrap();
+        assert!(resumed
+            .board
+            .is_marked(&Border::new(Position::new(1, 0), Position::new(1, 1))));
+    }
+
+    #[test]
+    fn clear_board_should_be_a_single_undoable_step() {
+        let mut state = small_state();
+        assert!(!state.clear_board());
+        state.toggle_border(0, 1, 0, 2).unwrap();
+        state.toggle_border(1, 1, 1, 2).unwrap();
+        state.toggle_marker(0, 0, 0, 1).unwrap();
+        state.assign_cell(0, 2, 0, 4).unwrap();
+        let before = walls(&state);
+
+        assert!(state.clear_board());
+        assert!(walls(&state).is_empty());
+        assert_eq!(state.board.get_markers().count(), 0);
+        assert_eq!(state.ownership.get_owner(&Position::new(0, 2)), None);
+
+        state.undo();
+        assert_eq!(walls(&state), before);
+        assert_eq!(state.board.get_markers().count(), 1);
+        assert_eq!(
+            state.ownership.get_owner(&Position::new(0, 2)),
+            Some(Position::new(0, 4))
+        );
+
+        state.redo();
+        assert!(walls(&state).is_empty());
+    }
+
+    #[test]
+    fn strokes_should_follow_the_first_edge() {
+        let mut state = small_state();
+        let border = |r1, c1, r2, c2| Border::new(Position::new(r1, c1), Position::new(r2, c2));
+        state.toggle_border(1, 1, 1, 2).unwrap();
+
+        assert!(state
+            .apply_stroke(vec![
+                border(0, 1, 0, 2),
+                border(1, 1, 1, 2),
+                border(1, 2, 2, 2),
+            ])
+            .is_ok());
+        assert_eq!(walls(&state).len(), 2);
+
+        state.undo();
+        assert_eq!(walls(&state).len(), 1);
+        state.redo();
+
+        assert!(state
+            .apply_stroke(vec![border(0, 1, 0, 2), border(0, 2, 1, 2)])
+            .is_ok());
+        assert_eq!(
+            walls(&state),
+            vec![(Position::new(1, 1), Position::new(1, 2))]
+        );
+        assert!(state.apply_stroke(vec![border(0, 2, 0, 3)]).is_err());
+    }
+
+    #[test]
+    fn jumping_should_restore_an_abandoned_branch() {
+        let mut state = small_state();
+        state.toggle_border(0, 1, 0, 2).unwrap();
+        state.assign_cell(0, 2, 0, 4).unwrap();
+        let abandoned = walls(&state);
+        state.undo();
+        state.undo();
+        state.toggle_border(1, 1, 1, 2).unwrap();
+
+        assert!(state.jump_to(2));
+        assert_eq!(walls(&state), abandoned);
+        assert_eq!(
+            state.ownership.get_owner(&Position::new(0, 2)),
+            Some(Position::new(0, 4))
+        );
+
+        assert!(state.jump_to(3));
+        assert_eq!(
+            walls(&state),
+            vec![(Position::new(1, 1), Position::new(1, 2))]
+        );
+        assert_eq!(state.ownership.get_owner(&Position::new(0, 2)), None);
+        assert!(!state.jump_to(4));
+    }
+
+    #[test]
+    fn abandoning_a_trial_should_roll_back_to_its_checkpoint() {
+        let mut state = small_state();
+        state.toggle_border(0, 1, 0, 2).unwrap();
+        state.begin_trial();
+        state.toggle_border(1, 1, 1, 2).unwrap();
+        assert_eq!(
+            StateView::from(&state).vertical_trial_walls,
+            vec![vec![false, false], vec![false, true]]
+        );
+
+        assert!(state.abandon_trial());
+        assert_eq!(
+            walls(&state),
+            vec![(Position::new(0, 1), Position::new(0, 2))]
+        );
+        assert_eq!(StateView::from(&state).trial_depth, 0);
+        assert!(!state.abandon_trial());
+        // The abandoned move can still be reached
+        assert!(state.jump_to(2));
+        assert_eq!(walls(&state).len(), 2);
+    }
+
+    #[test]
+    fn trials_should_nest() {
+        let mut state = small_state();
+        state.begin_trial();
+        state.toggle_border(0, 1, 0, 2).unwrap();
+        state.begin_trial();
+        state.toggle_border(1, 1, 1, 2).unwrap();
+        assert_eq!(StateView::from(&state).trial_depth, 2);
+
+        assert!(state.abandon_trial());
+        assert_eq!(
+            walls(&state),
+            vec![(Position::new(0, 1), Position::new(0, 2))]
+        );
+
+        state.begin_trial();
+        state.toggle_border(0, 2, 1, 2).unwrap();
+        assert!(state.commit_trial());
+        let view = StateView::from(&state);
+        assert_eq!(view.trial_depth, 1);
+        assert_eq!(
+            view.vertical_trial_walls,
+            vec![vec![false, true], vec![false, false]]
+        );
+        assert_eq!(view.horizontal_trial_walls, vec![vec![false, false, true]]);
+
+        assert!(state.abandon_trial());
+        assert!(walls(&state).is_empty());
+        assert!(!state.commit_trial());
+    }
+
+    #[test]
+    fn trials_should_survive_export() {
+        let mut state = small_state();
+        state.begin_trial();
+        state.toggle_border(0, 1, 0, 2).unwrap();
+        let mut resumed = GameState::import(&state.export()).unwrap();
+        assert!(resumed.abandon_trial());
+        assert!(walls(&resumed).is_empty());
+    }
+
+    fn take_hint(state: &mut GameState) -> Border {
+        let before: HashSet<Border> = state.objective.walls.clone();
+        state.take_hint();
+        *state.objective.walls.difference(&before).next().unwrap()
+    }
+
+    #[test]
+    fn hints_should_be_recorded_and_locked() {
+        let mut state = small_state();
+        let hint = take_hint(&mut state);
+        assert!(state.board.is_active(&hint));
+        assert!(state
+            .toggle_border(
+                hint.p1().row,
+                hint.p1().column,
+                hint.p2().row,
+                hint.p2().column
+            )
+            .is_err());
+
+        let view = StateView::from(&state);
+        assert_eq!((view.hints_used, view.hints_on_board), (1, 1));
+        assert!(view.has_past);
+
+        state.undo();
+        assert!(!state.board.is_active(&hint));
+        assert!(!state.objective.walls.contains(&hint));
+        let view = StateView::from(&state);
+        assert_eq!((view.hints_used, view.hints_on_board), (1, 0));
+
+        state.redo();
+        assert!(state.objective.walls.contains(&hint));
+    }
+
+    #[test]
+    fn hints_should_be_a_barrier_for_undo_if_configured() {
+        let mut state = small_state();
+        state.hint_undo = HintUndo::Barrier;
+        state.toggle_border(1, 0, 1, 1).unwrap();
+        state.begin_trial();
+        let hint = take_hint(&mut state);
+
+        state.undo();
+        assert!(state.board.is_active(&hint));
+        assert!(!state.jump_to(0));
+        assert!(!state.abandon_trial());
+        assert_eq!(StateView::from(&state).trial_depth, 1);
+        assert!(state.board.is_active(&hint));
+    }
+
+    #[test]
+    fn painting_should_not_remove_hint_walls() {
+        let mut state = small_state();
+        let wall = Border::new(Position::new(0, 0), Position::new(0, 1));
+        state.apply(&Hint { wall, taken: true });
+        state.assign_cell(0, 0, 1, 1).unwrap();
+        state.assign_cell(0, 1, 1, 1).unwrap();
+        assert!(state.board.is_active(&wall));
+        assert!(state.apply_stroke(vec![wall]).is_err());
+    }
+
+    #[test]
+    fn strict_input_should_refuse_walls_through_centers() {
+        let mut state = small_state();
+        state.strictness = Strictness::Strict;
+        assert_eq!(
+            state.toggle_border(0, 0, 0, 1),
+            Err(MoveRejection::CutsCenter)
+        );
+        assert_eq!(
+            state.toggle_border(0, 0, 1, 1),
+            Err(MoveRejection::NotAdjacent)
+        );
+        assert_eq!(
+            state.toggle_border(0, 2, 0, 3),
+            Err(MoveRejection::OutsideBoard)
+        );
+        assert_eq!(state.toggle_border(0, 1, 0, 2), Ok(()));
+        assert!(!state
+            .history
+            .get_entries()
+            .any(|entry| entry.get_borders().len() > 1));
+
+        state.set_strictness(Strictness::Relaxed);
+        assert_eq!(state.toggle_border(0, 0, 0, 1), Ok(()));
+    }
+
+    #[test]
+    fn strict_painting_should_refuse_walls_through_centers() {
+        let galaxies = [Galaxy::from([(0, 0), (0, 1)]), Galaxy::from([(0, 2)])];
+        let mut state = GameState::new(Universe::from(galaxies.as_slice()));
+        state.set_strictness(Strictness::Strict);
+        state.assign_cell(0, 2, 0, 4).unwrap();
+        let before = walls(&state);
+
+        assert_eq!(
+            state.assign_cell(0, 1, 0, 4),
+            Err(MoveRejection::CutsCenter)
+        );
+        assert_eq!(state.ownership.get_owner(&Position::new(0, 1)), None);
+        assert_eq!(walls(&state), before);
+        assert_eq!(state.history.get_entries().count(), 1);
+
+        state.set_strictness(Strictness::Relaxed);
+        assert_eq!(state.assign_cell(0, 1, 0, 4), Ok(()));
+        assert!(state
+            .board
+            .is_wall(Position::new(0, 0), Position::new(0, 1)));
+    }
+
+    #[test]
+    fn locked_walls_should_be_refused_whatever_the_strictness() {
+        let mut state = small_state();
+        let wall = Border::new(Position::new(0, 1), Position::new(0, 2));
+        state.objective.walls.insert(wall);
+        state.board.add_wall(wall.p1(), wall.p2());
+        assert_eq!(
+            state.toggle_border(0, 1, 0, 2),
+            Err(MoveRejection::LockedWall)
+        );
+        assert_eq!(
+            state.toggle_marker(0, 1, 0, 2),
+            Err(MoveRejection::MarkerOnWall)
+        );
+        assert_eq!(state.apply_stroke(vec![wall]), Err(MoveRejection::NoChange));
+    }
+
+    fn solve(state: &mut GameState) {
+        state.toggle_border(0, 1, 0, 2).unwrap();
+        state.toggle_border(1, 1, 1, 2).unwrap();
+        state.toggle_border(0, 2, 1, 2).unwrap();
+    }
+
+    #[test]
+    fn stats_should_count_moves_undos_and_checks() {
+        let mut state = small_state();
+        state.toggle_border(0, 0, 0, 1).unwrap();
+        state.toggle_marker(1, 0, 1, 1).unwrap();
+        state.check_solution();
+        state.undo();
+        state.take_hint();
+        state.check_solution();
+
+        let stats = StateView::from(&state).stats;
+        assert_eq!(stats.moves, 2);
+        assert_eq!(stats.undos, 1);
+        assert_eq!(stats.checks, 2);
+        assert_eq!(stats.errors_per_check.len(), 2);
+        assert!(stats.errors_per_check[0] > 0);
+    }
+
+    #[test]
+    fn solving_should_stop_the_timer_and_summarize() {
+        let mut state = small_state();
+        state.resume_timer(1000.0);
+        state.tick_timer(4000.0);
+        state.pause_timer(5000.0);
+        state.resume_timer(60_000.0);
+        state.tick_timer(62_000.0);
+        solve(&mut state);
+        assert!(state.completion.is_none());
+
+        state.check_solution();
+        let view = StateView::from(&state);
+        assert!(view.is_solved);
+        assert!(!view.timer_running);
+        let summary = view.completion.unwrap();
+        assert_eq!(summary.elapsed_ms, 6000);
+        assert_eq!((summary.width, summary.height), (3, 2));
+        assert_eq!(summary.stats.moves, 3);
+        assert_eq!(summary.stats.errors_per_check, vec![0]);
+
+        // The summary is produced once, and the timer stays stopped
+        state.resume_timer(70_000.0);
+        state.undo();
+        state.redo();
+        state.check_solution();
+        assert_eq!(state.completion.as_ref().unwrap().stats.checks, 1);
+        assert!(!state.timer.is_running());
+    }
+
+    #[test]
+    fn timer_should_be_saved_paused() {
+        let mut state = small_state();
+        state.resume_timer(0.0);
+        state.tick_timer(2500.0);
+        state.check_solution();
+        let resumed = GameState::import(&state.export()).unwrap();
+        assert!(!resumed.timer.is_running());
+        assert_eq!(resumed.timer.get_elapsed_ms(), 2500);
+        assert_eq!(resumed.stats.checks, 1);
+    }
+
+    #[test]
+    fn locating_a_mistake_should_point_at_its_region_until_the_next_move() {
+        let mut state = small_state();
+        assert!(!state.request_hint(HintTier::LocateMistake));
+        state.toggle_border(0, 1, 0, 2).unwrap();
+        state.toggle_border(1, 1, 1, 2).unwrap();
+        state.toggle_border(0, 0, 1, 0).unwrap();
+
+        assert!(state.request_hint(HintTier::LocateMistake));
+        let view = StateView::from(&state);
+        assert_eq!(
+            view.mistake_region,
+            [(0, 0), (0, 1), (1, 0), (1, 1)]
+                .into_iter()
+                .map(Position::from)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(view.stats.hint_cost, HintTier::LocateMistake.cost());
+        assert_eq!(walls(&state).len(), 3);
+
+        state.toggle_border(0, 0, 1, 0).unwrap();
+        assert!(StateView::from(&state).mistake_region.is_empty());
+
+        state.toggle_border(0, 0, 1, 0).unwrap();
+        assert!(state.request_hint(HintTier::LocateMistake));
+        let moves = state.stats.moves;
+        state.toggle_marker(0, 0, 0, 1).unwrap();
+        assert!(StateView::from(&state).mistake_region.is_empty());
+        assert_eq!(state.stats.moves, moves + 1);
+    }
+
+    #[test]
+    fn revealing_a_galaxy_should_lock_its_boundary_in_one_step() {
+        let mut state = small_state();
+        state.toggle_border(0, 0, 1, 0).unwrap();
+
+        assert!(state.request_hint(HintTier::RevealGalaxy));
+        let big_galaxy = [
+            Border::new(Position::new(0, 1), Position::new(0, 2)),
+            Border::new(Position::new(1, 1), Position::new(1, 2)),
+        ];
+        assert!(big_galaxy
+            .iter()
+            .all(|wall| state.objective.walls.contains(wall) && state.board.is_active(wall)));
+        let view = StateView::from(&state);
+        assert_eq!(view.hints_on_board, 2);
+        assert_eq!(view.stats.moves, 1);
+        let tier = &view.hint_tiers[HintTier::RevealGalaxy as usize];
+        assert_eq!((tier.used, tier.cost), (1, HintTier::RevealGalaxy.cost()));
+
+        state.undo();
+        assert!(big_galaxy.iter().all(|wall| !state.board.is_active(wall)));
+        assert!(state
+            .board
+            .is_active(&Border::new(Position::new(0, 0), Position::new(1, 0))));
+
+        // The next galaxy is revealed once the first one is locked
+        state.redo();
+        assert!(state.request_hint(HintTier::RevealGalaxy));
+        assert!(state
+            .objective
+            .walls
+            .contains(&Border::new(Position::new(0, 2), Position::new(1, 2))));
+        assert!(!state.request_hint(HintTier::RevealGalaxy));
+        assert!(!state.request_hint(HintTier::RevealWall));
+        assert_eq!(state.hints_used, 2);
+    }
+
+    #[test]
+    fn progress_should_ignore_hint_walls_and_count_checks() {
+        let mut state = small_state();
+        state.toggle_border(0, 0, 0, 1).unwrap();
+        state.take_hint();
+
+        let report = state.check_progress(true);
+        assert_eq!(
+            report.wrong_walls,
+            vec![Border::new(Position::new(0, 0), Position::new(0, 1))]
+        );
+        assert_eq!(report.missing_walls, Some(2));
+        assert_eq!(state.stats.progress_checks, 1);
+        assert_eq!(state.stats.checks, 0);
+        assert!(state.error.is_none());
+    }
+
+    #[test]
+    fn solvability_should_point_at_the_conflicting_walls() {
+        let mut state = small_state();
+        state.toggle_border(0, 1, 0, 2).unwrap();
+        assert_eq!(state.check_solvable(), Solvability::Solvable);
+
+        state.toggle_border(0, 2, 1, 2).unwrap();
+        state.toggle_border(0, 0, 0, 1).unwrap();
+        assert_eq!(
+            state.check_solvable(),
+            Solvability::Unsolvable {
+                conflicting_walls: vec![Border::new(Position::new(0, 0), Position::new(0, 1))]
+            }
+        );
+    }
+
+    #[test]
+    fn revealing_the_solution_should_keep_the_board_and_give_up() {
+        let mut state = small_state();
+        state.resume_timer(0.0);
+        state.toggle_border(0, 0, 0, 1).unwrap();
+        state.toggle_border(0, 1, 0, 2).unwrap();
+        state.tick_timer(3000.0);
+
+        let diff = state.reveal_solution();
+        assert_eq!(diff.walls_to_add.len(), 2);
+        assert_eq!(
+            diff.walls_to_remove,
+            vec![Border::new(Position::new(0, 0), Position::new(0, 1))]
+        );
+        assert_eq!(diff.correct_walls.len(), 1);
+        assert_eq!(walls(&state).len(), 2);
+        assert!(state.stats.gave_up);
+        assert!(!state.timer.is_running());
+
+        state.resume_timer(4000.0);
+        assert!(!state.timer.is_running());
+        let resumed = GameState::import(&state.export()).unwrap();
+        assert_eq!(StateView::from(&resumed).solution_diff, Some(diff));
+    }
+
+    #[test]
+    fn keyboard_should_toggle_edges_around_the_cursor() {
+        let mut state = small_state();
+        assert!(state.cursor_to_center(false));
+        assert_eq!(state.cursor.get_position(), Position::new(2, 4));
+        assert_eq!(
+            state.toggle_edge_towards(Direction::Right),
+            Err(MoveRejection::OutsideBoard)
+        );
+        state.toggle_edge_towards(Direction::Up).unwrap();
+        assert!(state.move_cursor(Direction::Left));
+        state.toggle_edge_towards(Direction::Down).unwrap();
+        assert_eq!(
+            walls(&state),
+            vec![
+                (Position::new(0, 2), Position::new(1, 2)),
+                (Position::new(1, 1), Position::new(1, 2)),
+            ]
+        );
+        assert_eq!(StateView::from(&state).cursor, Position::new(2, 3));
+
+        state.undo();
+        assert!(state.jump_cursor(Direction::Up));
+        assert!(!state.jump_cursor(Direction::Up));
+        assert_eq!(StateView::from(&state).cursor, Position::new(0, 3));
+    }
+
+    #[test]
+    fn view_should_fill_finished_galaxies() {
+        let mut state = small_state();
+        state.toggle_border(0, 2, 1, 2).unwrap();
+        state.toggle_border(0, 1, 0, 2).unwrap();
+        let view = StateView::from(&state);
+        assert_eq!(view.regions.ids, vec![vec![0, 0, 1], vec![0, 0, 0]]);
+        assert!(!view.regions.regions[0].complete);
+        assert!(view.regions.regions[1].complete);
+    }
+
+    #[test]
+    fn undoing_a_revealed_galaxy_should_keep_the_players_walls() {
+        let mut state = small_state();
+        state.toggle_border(0, 1, 0, 2).unwrap();
+        assert!(state.request_hint(HintTier::RevealGalaxy));
+        assert_eq!(
+            state.history.get_current_entry(),
+            Some(&Compound(vec![Hint {
+                wall: Border::new(Position::new(0, 2), Position::new(1, 2)),
+                taken: true,
+            }]))
+        );
+
+        state.undo();
+        assert_eq!(
+            walls(&state),
+            vec![(Position::new(0, 1), Position::new(0, 2))]
+        );
+        assert!(state.objective.walls.is_empty());
+    }
 }
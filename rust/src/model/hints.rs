@@ -0,0 +1,128 @@
+use crate::model::board::Board;
+use crate::model::border::Border;
+use crate::model::stats::Stats;
+use crate::model::universe::Universe;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use ts_rs::TS;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// How much a hint gives away, from least to most
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize, TS)]
+pub enum HintTier {
+    /// Points at the region with the first wrong wall, without fixing it
+    LocateMistake,
+    /// Reveals a single wall of the solution
+    RevealWall,
+    /// Reveals the whole boundary of a galaxy of the solution
+    RevealGalaxy,
+}
+
+impl HintTier {
+    pub const ALL: [HintTier; 3] = [
+        HintTier::LocateMistake,
+        HintTier::RevealWall,
+        HintTier::RevealGalaxy,
+    ];
+
+    /// Returns what the hint adds to the hint cost of the game
+    pub fn cost(&self) -> usize {
+        match self {
+            HintTier::LocateMistake => 1,
+            HintTier::RevealWall => 2,
+            HintTier::RevealGalaxy => 5,
+        }
+    }
+
+    /// Returns the number of hints of this tier counted in the stats
+    pub fn get_uses(&self, stats: &Stats) -> usize {
+        match self {
+            HintTier::LocateMistake => stats.mistakes_located,
+            HintTier::RevealWall => stats.walls_revealed,
+            HintTier::RevealGalaxy => stats.galaxies_revealed,
+        }
+    }
+}
+
+/// A hint tier as the UI shows it
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct HintTierView {
+    pub tier: HintTier,
+    pub cost: usize,
+    /// The number of hints of this tier taken, including undone ones
+    pub used: usize,
+}
+
+impl HintTierView {
+    pub fn all(stats: &Stats) -> Vec<HintTierView> {
+        HintTier::ALL
+            .iter()
+            .map(|tier| HintTierView {
+                tier: *tier,
+                cost: tier.cost(),
+                used: tier.get_uses(stats),
+            })
+            .collect()
+    }
+}
+
+/// Returns the walls between the galaxies of the solution, inside the board
+pub fn get_solution_walls(solution: &Universe, board: &Board) -> BTreeSet<Border> {
+    solution
+        .get_galaxies()
+        .iter()
+        .flat_map(|galaxy| galaxy.get_borders())
+        .filter(|border| board.contains_border(border))
+        .collect()
+}
+
+/// Returns the first wall on the board that isn't in the solution, if any
+pub fn find_mistake(board: &Board, solution_walls: &BTreeSet<Border>) -> Option<Border> {
+    board
+        .get_borders()
+        .filter(|wall| !solution_walls.contains(wall))
+        .min()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::board::Board;
+    use crate::model::border::Border;
+    use crate::model::galaxy::Galaxy;
+    use crate::model::hints::{find_mistake, get_solution_walls, HintTier};
+    use crate::model::position::Position;
+    use crate::model::universe::Universe;
+
+    fn border(r1: i32, c1: i32, r2: i32, c2: i32) -> Border {
+        Border::new(Position::new(r1, c1), Position::new(r2, c2))
+    }
+
+    #[test]
+    fn mistakes_should_be_walls_outside_the_solution_in_order() {
+        let galaxies = [
+            Galaxy::from([(0, 0), (0, 1)]),
+            Galaxy::from([(1, 0)]),
+            Galaxy::from([(1, 1)]),
+        ];
+        let universe = Universe::from(galaxies.as_slice());
+        let mut board = Board::new(2, 2);
+        let solution_walls = get_solution_walls(&universe, &board);
+        assert_eq!(solution_walls.len(), 3);
+        assert_eq!(find_mistake(&board, &solution_walls), None);
+
+        board.add_wall(Position::new(1, 0), Position::new(1, 1));
+        board.add_wall(Position::new(0, 1), Position::new(0, 0));
+        assert_eq!(
+            find_mistake(&board, &solution_walls),
+            Some(border(0, 0, 0, 1))
+        );
+    }
+
+    #[test]
+    fn costs_should_grow_with_the_tier() {
+        let costs: Vec<usize> = HintTier::ALL.iter().map(HintTier::cost).collect();
+        assert!(costs.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+}
@@ -1,73 +1,648 @@
 use crate::model::border::Border;
+use crate::model::compact::{write_varint, CompactError, Edges, Reader};
+use crate::model::position::Position;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
 
+/// Identifies a state in the history. The root, before any move, is 0.
+pub type NodeId = usize;
+
+const ENTRY_TOGGLE_BORDER: u8 = 0;
+const ENTRY_TOGGLE_MARKER: u8 = 1;
+const ENTRY_COMPOUND: u8 = 2;
+const ENTRY_HINT_TAKEN: u8 = 3;
+const ENTRY_HINT_RETURNED: u8 = 4;
+const ENTRY_PAINT: u8 = 5;
+
+const POLICY_TREE: u8 = 0;
+const POLICY_LINEAR: u8 = 1;
+
+/// The game never nests compounds, this only bounds the recursion when reading bad input
+const MAX_COMPOUND_NESTING: usize = 4;
+
+/// Every state the board has been in, as a tree of moves. Undoing moves to the parent,
+/// and redoing moves to the child that was created or visited last.
+#[derive(Clone, Serialize, Deserialize)]
 pub struct History {
-    entries: Vec<HistoryEntry>,
-    current_index: usize,
+    nodes: Vec<HistoryNode>,
+    current: NodeId,
+    #[serde(default)]
+    policy: HistoryPolicy,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct HistoryNode {
+    /// The move from the parent to this node, None only for the root
+    entry: Option<HistoryEntry>,
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+    /// The child that redo moves to
+    redo_child: Option<NodeId>,
+}
+
+/// Whether undo can take back a hint
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum HintUndo {
+    /// Undoing a hint removes its wall again. The hint still counts as used.
+    #[default]
+    Revertible,
+    /// Undo stops at the last hint, so moves before it can't be taken back
+    Barrier,
+}
+
+/// What happens to undone moves when a new move is made
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum HistoryPolicy {
+    /// The new move starts a branch, and the undone moves stay reachable on theirs
+    #[default]
+    Tree,
+    /// The undone moves and their inverses are added before the new move, on a single
+    /// line, so that undoing far enough brings them back
+    Linear,
 }
 
-#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Serialize, Deserialize)]
 pub enum HistoryEntry {
     ToggleBorder(Border),
+    /// An edge marked as definitely not a wall, or unmarked
+    ToggleMarker(Border),
+    /// Entries applied in order, and undone in reverse order, as a single step
+    Compound(Vec<HistoryEntry>),
+    /// A wall of the solution revealed to the player, which is locked on the board.
+    /// Its inverse, with `taken` false, removes the wall and unlocks it.
+    Hint {
+        wall: Border,
+        taken: bool,
+    },
+    /// Cells painted into centers, and the walls toggled to follow the new boundaries
+    Paint {
+        cells: Vec<CellChange>,
+        borders: Vec<Border>,
+    },
+}
+
+/// A cell that changed owner, where the owners are centers in half-steps
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Debug, Serialize, Deserialize)]
+pub struct CellChange {
+    pub cell: Position,
+    pub before: Option<Position>,
+    pub after: Option<Position>,
+}
+
+impl HistoryEntry {
+    /// Returns the borders affected by this entry, walls or markers
+    pub fn get_borders(&self) -> Vec<Border> {
+        match self {
+            HistoryEntry::ToggleBorder(border)
+            | HistoryEntry::ToggleMarker(border)
+            | HistoryEntry::Hint { wall: border, .. } => vec![*border],
+            HistoryEntry::Paint { borders, .. } => borders.clone(),
+            HistoryEntry::Compound(entries) => {
+                entries.iter().flat_map(HistoryEntry::get_borders).collect()
+            }
+        }
+    }
+
+    /// Returns the cells whose owner is changed by this entry
+    pub fn get_cells(&self) -> Vec<Position> {
+        match self {
+            HistoryEntry::ToggleBorder(_)
+            | HistoryEntry::ToggleMarker(_)
+            | HistoryEntry::Hint { .. } => vec![],
+            HistoryEntry::Paint { cells, .. } => cells.iter().map(|change| change.cell).collect(),
+            HistoryEntry::Compound(entries) => {
+                entries.iter().flat_map(HistoryEntry::get_cells).collect()
+            }
+        }
+    }
+
+    /// Returns the entry that reverts this one
+    pub fn inverse(&self) -> HistoryEntry {
+        match self {
+            HistoryEntry::ToggleBorder(_) | HistoryEntry::ToggleMarker(_) => self.clone(),
+            HistoryEntry::Paint { cells, borders } => HistoryEntry::Paint {
+                cells: cells
+                    .iter()
+                    .map(|change| CellChange {
+                        cell: change.cell,
+                        before: change.after,
+                        after: change.before,
+                    })
+                    .collect(),
+                borders: borders.clone(),
+            },
+            HistoryEntry::Compound(entries) => {
+                HistoryEntry::Compound(entries.iter().rev().map(HistoryEntry::inverse).collect())
+            }
+            HistoryEntry::Hint { wall, taken } => HistoryEntry::Hint {
+                wall: *wall,
+                taken: !taken,
+            },
+        }
+    }
+
+    /// Returns the change in the number of hint walls on the board made by this entry
+    pub fn get_hint_change(&self) -> isize {
+        match self {
+            HistoryEntry::Hint { taken: true, .. } => 1,
+            HistoryEntry::Hint { taken: false, .. } => -1,
+            HistoryEntry::Compound(entries) => {
+                entries.iter().map(HistoryEntry::get_hint_change).sum()
+            }
+            _ => 0,
+        }
+    }
+}
+
+impl HistoryEntry {
+    /// Returns a short description of the move, for showing it in the history
+    pub fn label(&self) -> String {
+        match self {
+            HistoryEntry::ToggleBorder(_) => "Wall".to_string(),
+            HistoryEntry::ToggleMarker(_) => "Marker".to_string(),
+            HistoryEntry::Paint { .. } => "Paint".to_string(),
+            HistoryEntry::Compound(entries) => format!("{} moves", entries.len()),
+            HistoryEntry::Hint { .. } => "Hint".to_string(),
+        }
+    }
+}
+
+impl HistoryEntry {
+    /// Writes the entry in the compact format of [History::write_compact]: its kind, followed
+    /// by the edge index of its wall, or by its parts for compounds and paint
+    fn write_compact(&self, bytes: &mut Vec<u8>, edges: &Edges) {
+        match self {
+            HistoryEntry::ToggleBorder(border) => {
+                bytes.push(ENTRY_TOGGLE_BORDER);
+                write_varint(bytes, edges.index(border));
+            }
+            HistoryEntry::ToggleMarker(border) => {
+                bytes.push(ENTRY_TOGGLE_MARKER);
+                write_varint(bytes, edges.index(border));
+            }
+            HistoryEntry::Compound(entries) => {
+                bytes.push(ENTRY_COMPOUND);
+                write_varint(bytes, entries.len());
+                for entry in entries {
+                    entry.write_compact(bytes, edges);
+                }
+            }
+            HistoryEntry::Hint { wall, taken } => {
+                bytes.push(if *taken {
+                    ENTRY_HINT_TAKEN
+                } else {
+                    ENTRY_HINT_RETURNED
+                });
+                write_varint(bytes, edges.index(wall));
+            }
+            HistoryEntry::Paint { cells, borders } => {
+                bytes.push(ENTRY_PAINT);
+                write_varint(bytes, cells.len());
+                for change in cells {
+                    write_position(bytes, &change.cell);
+                    write_owner(bytes, change.before);
+                    write_owner(bytes, change.after);
+                }
+                write_varint(bytes, borders.len());
+                for border in borders {
+                    write_varint(bytes, edges.index(border));
+                }
+            }
+        }
+    }
+
+    fn read_compact(
+        reader: &mut Reader,
+        edges: &Edges,
+        nesting: usize,
+    ) -> Result<HistoryEntry, CompactError> {
+        let entry = match reader.read_byte()? {
+            ENTRY_TOGGLE_BORDER => HistoryEntry::ToggleBorder(read_border(reader, edges)?),
+            ENTRY_TOGGLE_MARKER => HistoryEntry::ToggleMarker(read_border(reader, edges)?),
+            ENTRY_COMPOUND if nesting < MAX_COMPOUND_NESTING => {
+                let count = reader.read_varint()?;
+                let entries = (0..count)
+                    .map(|_| HistoryEntry::read_compact(reader, edges, nesting + 1))
+                    .collect::<Result<_, _>>()?;
+                HistoryEntry::Compound(entries)
+            }
+            kind @ (ENTRY_HINT_TAKEN | ENTRY_HINT_RETURNED) => HistoryEntry::Hint {
+                wall: read_border(reader, edges)?,
+                taken: kind == ENTRY_HINT_TAKEN,
+            },
+            ENTRY_PAINT => {
+                let count = reader.read_varint()?;
+                let cells = (0..count)
+                    .map(|_| {
+                        Ok(CellChange {
+                            cell: read_position(reader)?,
+                            before: read_owner(reader)?,
+                            after: read_owner(reader)?,
+                        })
+                    })
+                    .collect::<Result<_, _>>()?;
+                let count = reader.read_varint()?;
+                let borders = (0..count)
+                    .map(|_| read_border(reader, edges))
+                    .collect::<Result<_, _>>()?;
+                HistoryEntry::Paint { cells, borders }
+            }
+            _ => return Err(CompactError::InvalidEncoding),
+        };
+        Ok(entry)
+    }
+}
+
+impl Default for History {
+    fn default() -> Self {
+        History::new()
+    }
 }
 
 impl History {
     pub fn new() -> Self {
         History {
-            entries: Vec::new(),
-            current_index: 0,
+            nodes: vec![HistoryNode {
+                entry: None,
+                parent: None,
+                children: Vec::new(),
+                redo_child: None,
+            }],
+            current: 0,
+            policy: HistoryPolicy::default(),
         }
     }
 
+    pub fn get_policy(&self) -> HistoryPolicy {
+        self.policy
+    }
+
+    pub fn set_policy(&mut self, policy: HistoryPolicy) {
+        self.policy = policy;
+    }
+
+    /// Returns the moves of every node, on every branch
+    pub fn get_entries(&self) -> impl Iterator<Item = &HistoryEntry> {
+        self.nodes.iter().filter_map(|node| node.entry.as_ref())
+    }
+
+    /// Returns true iff the nodes form a tree rooted at 0 and the current node is in it,
+    /// which is not guaranteed for a deserialized history
+    pub fn is_consistent(&self) -> bool {
+        let Some(root) = self.nodes.first() else {
+            return false;
+        };
+        if root.entry.is_some() || root.parent.is_some() || self.current >= self.nodes.len() {
+            return false;
+        }
+        self.nodes.iter().enumerate().all(|(id, node)| {
+            let linked_to_parent = match node.parent {
+                // Parents come first, which rules out cycles
+                Some(parent) => {
+                    parent < id && node.entry.is_some() && self.nodes[parent].children.contains(&id)
+                }
+                None => id == 0,
+            };
+            let linked_to_children = node
+                .children
+                .iter()
+                .all(|&child| self.nodes.get(child).and_then(|it| it.parent) == Some(id));
+            let redo_child_valid = node
+                .redo_child
+                .is_none_or(|child| node.children.contains(&child));
+            linked_to_parent && linked_to_children && redo_child_valid
+        })
+    }
+
     pub fn has_past(&self) -> bool {
-        self.current_index > 0
+        self.nodes[self.current].parent.is_some()
     }
 
     pub fn has_future(&self) -> bool {
-        self.current_index < self.entries.len()
+        self.nodes[self.current].redo_child.is_some()
+    }
+
+    /// Returns true iff the node is in the tree
+    pub fn contains(&self, node: NodeId) -> bool {
+        node < self.nodes.len()
+    }
+
+    pub fn get_current(&self) -> NodeId {
+        self.current
+    }
+
+    /// Returns the entry that undo would revert, if any
+    pub fn get_current_entry(&self) -> Option<&HistoryEntry> {
+        self.nodes[self.current].entry.as_ref()
+    }
+
+    /// Returns the entries from the start to the current node, which together make up
+    /// the current state
+    pub fn get_past(&self) -> Vec<&HistoryEntry> {
+        let mut past = Vec::new();
+        let mut node = self.current;
+        while let Some(parent) = self.nodes[node].parent {
+            past.extend(self.nodes[node].entry.as_ref());
+            node = parent;
+        }
+        past.reverse();
+        past
+    }
+
+    /// Returns the nodes reached by the moves made from the node, oldest first
+    pub fn get_children(&self, node: NodeId) -> &[NodeId] {
+        self.nodes
+            .get(node)
+            .map(|node| node.children.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Returns the last node of every branch, oldest first
+    pub fn get_branches(&self) -> Vec<NodeId> {
+        (0..self.nodes.len())
+            .filter(|&id| self.nodes[id].children.is_empty())
+            .collect()
     }
 
     /// Undo the last action in the history, if any, and return it
     pub fn undo(&mut self) -> Option<&HistoryEntry> {
-        if self.has_past() {
-            self.current_index -= 1;
-            self.entries.get(self.current_index)
-        } else {
-            None
-        }
+        let undone = self.current;
+        let parent = self.nodes[undone].parent?;
+        self.nodes[parent].redo_child = Some(undone);
+        self.current = parent;
+        self.nodes[undone].entry.as_ref()
     }
 
-    /// Redo the last undone action in the history, if any, and return it
+    /// Redo the last undone action on the current branch, if any, and return it
     pub fn redo(&mut self) -> Option<&HistoryEntry> {
-        if self.has_future() {
-            self.current_index += 1;
-            self.entries.get(self.current_index - 1)
-        } else {
-            None
-        }
+        let child = self.nodes[self.current].redo_child?;
+        self.current = child;
+        self.nodes[child].entry.as_ref()
     }
 
+    /// Adds the entry after the current one. What happens to undone entries
+    /// depends on the [HistoryPolicy].
     pub fn push(&mut self, entry: HistoryEntry) {
-        if self.has_future() {
-            let mut future = self.entries[self.current_index..]
-                .iter()
-                .cloned()
-                .rev()
-                .collect();
-            self.entries.append(&mut future);
-            self.entries.push(entry);
-        } else {
-            self.entries.push(entry);
-        }
-        self.current_index = self.entries.len();
+        if self.policy == HistoryPolicy::Linear {
+            let mut future = Vec::new();
+            while let Some(entry) = self.redo() {
+                future.push(entry.clone());
+            }
+            for undone in future.iter().rev() {
+                self.add_child(undone.inverse());
+            }
+        }
+        self.add_child(entry);
         assert!(self.has_past());
         assert!(!self.has_future());
     }
+
+    /// Returns the moves that take the board from the current node to the target, in
+    /// order: the inverses of the moves up to the common ancestor, then the moves down to
+    /// the target. Returns None if there is no such node.
+    pub fn get_path(&self, target: NodeId) -> Option<Vec<HistoryEntry>> {
+        let (up, down) = self.get_path_nodes(target)?;
+        let steps = up
+            .iter()
+            .filter_map(|&node| self.nodes[node].entry.as_ref().map(HistoryEntry::inverse))
+            .chain(
+                down.iter()
+                    .filter_map(|&node| self.nodes[node].entry.clone()),
+            )
+            .collect();
+        Some(steps)
+    }
+
+    /// Moves to the node, and returns the moves that take the board there, see
+    /// [History::get_path]. Returns None if there is no such node.
+    pub fn jump_to(&mut self, target: NodeId) -> Option<Vec<HistoryEntry>> {
+        let steps = self.get_path(target)?;
+        let (_, down) = self.get_path_nodes(target)?;
+        for node in down {
+            // Redo follows the path that was jumped along
+            if let Some(parent) = self.nodes[node].parent {
+                self.nodes[parent].redo_child = Some(node);
+            }
+        }
+        self.current = target;
+        Some(steps)
+    }
+
+    /// Returns the nodes left on the way up from the current node to the common ancestor
+    /// with the target, and the nodes entered on the way down from there to the target
+    fn get_path_nodes(&self, target: NodeId) -> Option<(Vec<NodeId>, Vec<NodeId>)> {
+        if !self.contains(target) {
+            return None;
+        }
+        let ancestors = |mut node: NodeId| {
+            let mut path = vec![node];
+            while let Some(parent) = self.nodes[node].parent {
+                path.push(parent);
+                node = parent;
+            }
+            path
+        };
+        let mut up = ancestors(self.current);
+        let mut down = ancestors(target);
+        let common = *down.iter().find(|node| up.contains(node)).unwrap();
+        up.truncate(up.iter().position(|&node| node == common).unwrap());
+        down.truncate(down.iter().position(|&node| node == common).unwrap());
+        down.reverse();
+        Some((up, down))
+    }
+
+    /// Returns the tree in a form the UI can render
+    pub fn get_view(&self) -> HistoryView {
+        // The nodes that undo and redo can reach without jumping
+        let mut on_current_line = vec![false; self.nodes.len()];
+        let mut node = Some(self.current);
+        while let Some(id) = node {
+            on_current_line[id] = true;
+            node = self.nodes[id].parent;
+        }
+        let mut node = self.nodes[self.current].redo_child;
+        while let Some(id) = node {
+            on_current_line[id] = true;
+            node = self.nodes[id].redo_child;
+        }
+
+        let mut depths = vec![0; self.nodes.len()];
+        let nodes = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(id, node)| {
+                if let Some(parent) = node.parent {
+                    depths[id] = depths[parent] + 1;
+                }
+                HistoryNodeView {
+                    id,
+                    parent: node.parent,
+                    children: node.children.clone(),
+                    depth: depths[id],
+                    label: node
+                        .entry
+                        .as_ref()
+                        .map_or("Start".to_string(), HistoryEntry::label),
+                    on_current_line: on_current_line[id],
+                }
+            })
+            .collect();
+        HistoryView {
+            nodes,
+            current: self.current,
+            policy: self.policy,
+        }
+    }
+
+    /// Adds the entry as a child of the current node, which redo will follow, and moves to it
+    fn add_child(&mut self, entry: HistoryEntry) {
+        let id = self.nodes.len();
+        self.nodes.push(HistoryNode {
+            entry: Some(entry),
+            parent: Some(self.current),
+            children: Vec::new(),
+            redo_child: None,
+        });
+        let parent = &mut self.nodes[self.current];
+        parent.children.push(id);
+        parent.redo_child = Some(id);
+        self.current = id;
+    }
+}
+
+/// The history tree as the UI renders it
+#[derive(Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct HistoryView {
+    /// Every node, parents before their children, where the index is the id
+    pub nodes: Vec<HistoryNodeView>,
+    pub current: NodeId,
+    pub policy: HistoryPolicy,
+}
+
+#[derive(Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct HistoryNodeView {
+    pub id: NodeId,
+    pub parent: Option<NodeId>,
+    /// Oldest first
+    pub children: Vec<NodeId>,
+    /// The number of moves from the start
+    pub depth: usize,
+    pub label: String,
+    /// Whether undo and redo can reach the node without jumping
+    pub on_current_line: bool,
+}
+
+impl History {
+    /// Writes the history in the compact format of saved games, with walls as edge indices
+    /// and numbers as varints: the number of moves, then for each move the distance back to
+    /// its parent, doubled and plus one if redo follows it, and the move itself. The current
+    /// node and the policy come last. Children are listed in the order of their ids, so
+    /// they don't need to be written.
+    pub fn write_compact(&self, bytes: &mut Vec<u8>, edges: &Edges) {
+        write_varint(bytes, self.nodes.len() - 1);
+        for (id, node) in self.nodes.iter().enumerate().skip(1) {
+            let parent = node.parent.expect("only the root has no parent");
+            let followed = self.nodes[parent].redo_child == Some(id);
+            write_varint(bytes, 2 * (id - parent) + followed as usize);
+            node.entry
+                .as_ref()
+                .expect("only the root has no entry")
+                .write_compact(bytes, edges);
+        }
+        write_varint(bytes, self.current);
+        bytes.push(match self.policy {
+            HistoryPolicy::Tree => POLICY_TREE,
+            HistoryPolicy::Linear => POLICY_LINEAR,
+        });
+    }
+
+    /// Reads a history written by [History::write_compact]. The current node isn't checked,
+    /// see [History::is_consistent].
+    pub fn read_compact(reader: &mut Reader, edges: &Edges) -> Result<History, CompactError> {
+        let mut history = History::new();
+        let count = reader.read_varint()?;
+        for id in 1..=count {
+            let link = reader.read_varint()?;
+            let parent = id
+                .checked_sub(link / 2)
+                .filter(|&parent| parent < id)
+                .ok_or(CompactError::InvalidEncoding)?;
+            let entry = HistoryEntry::read_compact(reader, edges, 0)?;
+            history.nodes.push(HistoryNode {
+                entry: Some(entry),
+                parent: Some(parent),
+                children: Vec::new(),
+                redo_child: None,
+            });
+            let parent = &mut history.nodes[parent];
+            parent.children.push(id);
+            if link % 2 == 1 {
+                parent.redo_child = Some(id);
+            }
+        }
+        history.current = reader.read_varint()?;
+        history.policy = match reader.read_byte()? {
+            POLICY_TREE => HistoryPolicy::Tree,
+            POLICY_LINEAR => HistoryPolicy::Linear,
+            _ => return Err(CompactError::InvalidEncoding),
+        };
+        Ok(history)
+    }
+}
+
+fn read_border(reader: &mut Reader, edges: &Edges) -> Result<Border, CompactError> {
+    let index = reader.read_varint()?;
+    edges
+        .border(index)
+        .ok_or(CompactError::WallOutOfBounds(index))
+}
+
+fn write_position(bytes: &mut Vec<u8>, position: &Position) {
+    write_varint(bytes, position.row as usize);
+    write_varint(bytes, position.column as usize);
+}
+
+fn read_position(reader: &mut Reader) -> Result<Position, CompactError> {
+    let row = reader.read_varint()?;
+    let column = reader.read_varint()?;
+    match (i32::try_from(row), i32::try_from(column)) {
+        (Ok(row), Ok(column)) => Ok(Position::new(row, column)),
+        _ => Err(CompactError::InvalidEncoding),
+    }
+}
+
+/// Writes the center owning a cell, with the row shifted by one so that 0 can mean none
+fn write_owner(bytes: &mut Vec<u8>, owner: Option<Position>) {
+    match owner {
+        Some(center) => write_position(bytes, &Position::new(center.row + 1, center.column)),
+        None => bytes.push(0),
+    }
+}
+
+fn read_owner(reader: &mut Reader) -> Result<Option<Position>, CompactError> {
+    let row = reader.read_varint()?;
+    if row == 0 {
+        return Ok(None);
+    }
+    let column = reader.read_varint()?;
+    match (i32::try_from(row - 1), i32::try_from(column)) {
+        (Ok(row), Ok(column)) => Ok(Some(Position::new(row, column))),
+        _ => Err(CompactError::InvalidEncoding),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::model::border::Border;
-    use crate::model::history::{History, HistoryEntry};
+    use crate::model::compact::{Edges, Reader};
+    use crate::model::history::{CellChange, History, HistoryEntry, HistoryPolicy};
     use crate::model::position::Position;
 
     fn some_entry() -> HistoryEntry {
@@ -102,4 +677,163 @@ mod tests {
         assert_eq!(redo.unwrap(), &entry);
         assert!(!history.has_future());
     }
+
+    #[test]
+    fn should_keep_inverses_of_undone_entries() {
+        let paint = HistoryEntry::Paint {
+            cells: vec![CellChange {
+                cell: Position::new(0, 0),
+                before: None,
+                after: Some(Position::new(0, 0)),
+            }],
+            borders: vec![],
+        };
+        let mut history = History::new();
+        history.set_policy(HistoryPolicy::Linear);
+        history.push(paint.clone());
+        history.undo();
+        history.push(some_entry());
+
+        assert_eq!(history.undo(), Some(&some_entry()));
+        assert_eq!(history.undo(), Some(&paint.inverse()));
+        assert_eq!(history.undo(), Some(&paint));
+        assert!(!history.has_past());
+    }
+
+    #[test]
+    fn compound_entries_should_be_inverted_in_reverse_order() {
+        let paint = |before, after| HistoryEntry::Paint {
+            cells: vec![CellChange {
+                cell: Position::new(0, 0),
+                before,
+                after,
+            }],
+            borders: vec![],
+        };
+        let compound =
+            HistoryEntry::Compound(vec![some_entry(), paint(None, Some(Position::new(0, 0)))]);
+        assert_eq!(
+            compound.inverse(),
+            HistoryEntry::Compound(vec![paint(Some(Position::new(0, 0)), None), some_entry()])
+        );
+        assert_eq!(compound.inverse().inverse(), compound);
+    }
+
+    fn entry(column: i32) -> HistoryEntry {
+        HistoryEntry::ToggleBorder(Border::new(
+            Position::new(0, column),
+            Position::new(1, column),
+        ))
+    }
+
+    #[test]
+    fn new_moves_after_undo_should_start_a_branch() {
+        let mut history = History::new();
+        history.push(entry(0));
+        history.push(entry(1));
+        history.undo();
+        history.push(entry(2));
+
+        assert_eq!(history.get_children(1), &[2, 3]);
+        assert_eq!(history.get_branches(), vec![2, 3]);
+        assert_eq!(history.undo(), Some(&entry(2)));
+        assert_eq!(history.redo(), Some(&entry(2)));
+        assert!(!history.has_future());
+        assert!(history.is_consistent());
+    }
+
+    #[test]
+    fn jumping_should_undo_to_the_common_ancestor_and_redo_from_there() {
+        let mut history = History::new();
+        history.push(entry(0));
+        history.push(entry(1));
+        history.undo();
+        history.push(entry(2));
+
+        assert_eq!(history.jump_to(2), Some(vec![entry(2), entry(1)]));
+        assert_eq!(history.get_current(), 2);
+        assert_eq!(history.jump_to(0), Some(vec![entry(1), entry(0)]));
+        assert_eq!(history.jump_to(7), None);
+
+        // Redo follows the branch that was visited last
+        assert_eq!(history.redo(), Some(&entry(0)));
+        assert_eq!(history.redo(), Some(&entry(1)));
+        assert_eq!(history.redo(), None);
+    }
+
+    #[test]
+    fn view_should_mark_the_nodes_reachable_with_undo_and_redo() {
+        let mut history = History::new();
+        history.push(entry(0));
+        history.push(entry(1));
+        history.undo();
+        history.push(entry(2));
+        history.undo();
+
+        let view = history.get_view();
+        assert_eq!(view.current, 1);
+        let line: Vec<bool> = view.nodes.iter().map(|node| node.on_current_line).collect();
+        assert_eq!(line, vec![true, true, false, true]);
+        let depths: Vec<usize> = view.nodes.iter().map(|node| node.depth).collect();
+        assert_eq!(depths, vec![0, 1, 2, 2]);
+        assert_eq!(view.nodes[0].label, "Start");
+    }
+
+    #[test]
+    fn past_should_count_the_hints_on_the_board() {
+        let hint = HistoryEntry::Hint {
+            wall: Border::new(Position::new(0, 0), Position::new(1, 0)),
+            taken: true,
+        };
+        let mut history = History::new();
+        history.push(hint.clone());
+        history.push(HistoryEntry::Compound(vec![entry(1), hint.clone()]));
+        history.push(hint.inverse());
+
+        let hints: isize = history
+            .get_past()
+            .into_iter()
+            .map(HistoryEntry::get_hint_change)
+            .sum();
+        assert_eq!(hints, 1);
+        assert_eq!(history.get_current_entry(), Some(&hint.inverse()));
+    }
+
+    #[test]
+    fn compact_format_should_keep_the_branches_and_the_moves() {
+        let paint = HistoryEntry::Paint {
+            cells: vec![CellChange {
+                cell: Position::new(1, 2),
+                before: None,
+                after: Some(Position::new(1, 3)),
+            }],
+            borders: vec![Border::new(Position::new(0, 2), Position::new(1, 2))],
+        };
+        let hint = HistoryEntry::Hint {
+            wall: Border::new(Position::new(0, 1), Position::new(0, 2)),
+            taken: false,
+        };
+        let mut history = History::new();
+        history.push(entry(0));
+        history.push(entry(1));
+        history.undo();
+        history.push(paint);
+        history.push(HistoryEntry::Compound(vec![hint, entry(2)]));
+        history.jump_to(2);
+        history.undo();
+        history.set_policy(HistoryPolicy::Linear);
+
+        let edges = Edges::new(3, 2);
+        let mut bytes = Vec::new();
+        history.write_compact(&mut bytes, &edges);
+        let mut read = History::read_compact(&mut Reader::new(&bytes), &edges).unwrap();
+
+        assert!(read.is_consistent());
+        assert!(read.get_entries().eq(history.get_entries()));
+        assert_eq!(read.get_current(), 1);
+        assert_eq!(read.get_children(1), &[2, 3]);
+        assert_eq!(read.get_policy(), HistoryPolicy::Linear);
+        // Redo follows the branch that was visited last
+        assert_eq!(read.redo(), Some(&entry(1)));
+    }
 }
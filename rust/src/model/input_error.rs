@@ -0,0 +1,186 @@
+use crate::model::board::Board;
+use crate::model::border::Border;
+use crate::model::input_rules::MoveRejection;
+use crate::model::objective::Objective;
+use crate::model::position::Position;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use ts_rs::TS;
+
+/// The largest board that can be generated from JS, larger ones take too long
+pub const MAX_GENERATED_SIZE: usize = 30;
+
+/// What was wrong with the arguments passed from JS
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum InputErrorCode {
+    /// A cell isn't on the board
+    CellOutsideBoard,
+    /// The two cells of an edge aren't next to each other
+    CellsNotAdjacent,
+    /// A position isn't one of the centers of the puzzle
+    UnknownCenter,
+    /// A node isn't in the history
+    UnknownHistoryNode,
+    /// A board size is zero or too large
+    InvalidSize,
+    /// A value couldn't be read as the expected type
+    MalformedValue,
+    /// A saved game couldn't be resumed
+    InvalidSavedGame,
+    /// A move was refused by the rules, which is normally returned rather than thrown
+    RefusedMove,
+}
+
+/// Invalid arguments passed to a wasm entry point. It is thrown to JS as an `Error`
+/// whose message is this error as JSON, so that the code can be read back.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct InputError {
+    pub code: InputErrorCode,
+    pub message: String,
+}
+
+impl InputError {
+    pub fn new(code: InputErrorCode, message: impl Into<String>) -> Self {
+        InputError {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+/// Displays the error as JSON, which becomes the message of the `Error` thrown to JS
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let json = serde_json::to_string(self).map_err(|_| std::fmt::Error)?;
+        write!(f, "{json}")
+    }
+}
+
+impl std::error::Error for InputError {}
+
+impl From<serde_wasm_bindgen::Error> for InputError {
+    fn from(error: serde_wasm_bindgen::Error) -> Self {
+        InputError::new(InputErrorCode::MalformedValue, error.to_string())
+    }
+}
+
+/// Rejections caused by bad arguments keep their codes, the rest are [InputErrorCode::RefusedMove]
+impl From<MoveRejection> for InputError {
+    fn from(rejection: MoveRejection) -> Self {
+        let code = match rejection {
+            MoveRejection::OutsideBoard | MoveRejection::MirrorOutsideBoard => {
+                InputErrorCode::CellOutsideBoard
+            }
+            MoveRejection::NotAdjacent => InputErrorCode::CellsNotAdjacent,
+            MoveRejection::NotACenter => InputErrorCode::UnknownCenter,
+            MoveRejection::LockedWall
+            | MoveRejection::CutsCenter
+            | MoveRejection::MarkerOnWall
+            | MoveRejection::NoChange => InputErrorCode::RefusedMove,
+        };
+        InputError::new(code, rejection.to_string())
+    }
+}
+
+/// Returns the cell, if it's on the board
+pub fn check_cell(board: &Board, row: i32, column: i32) -> Result<Position, InputError> {
+    let cell = Position::new(row, column);
+    if board.contains(&cell) {
+        Ok(cell)
+    } else {
+        Err(InputError::new(
+            InputErrorCode::CellOutsideBoard,
+            format!("{cell} is not on the board"),
+        ))
+    }
+}
+
+/// Checks that both cells of the border are on the board
+pub fn check_border(board: &Board, border: &Border) -> Result<(), InputError> {
+    check_cell(board, border.p1().row, border.p1().column)?;
+    check_cell(board, border.p2().row, border.p2().column)?;
+    Ok(())
+}
+
+/// Returns the center, given in half-steps, if it's one of the puzzle's
+pub fn check_center(objective: &Objective, row: i32, column: i32) -> Result<Position, InputError> {
+    let center = Position::new(row, column);
+    if objective.centers.iter().any(|gc| gc.position == center) {
+        Ok(center)
+    } else {
+        Err(InputError::new(
+            InputErrorCode::UnknownCenter,
+            format!("{center} is not a center"),
+        ))
+    }
+}
+
+/// Checks that a board of the size can be generated
+pub fn check_size(size: usize) -> Result<usize, InputError> {
+    if (1..=MAX_GENERATED_SIZE).contains(&size) {
+        Ok(size)
+    } else {
+        Err(InputError::new(
+            InputErrorCode::InvalidSize,
+            format!("the size must be between 1 and {MAX_GENERATED_SIZE}, got {size}"),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::board::Board;
+    use crate::model::input_error::{check_cell, check_size, InputError, InputErrorCode};
+    use crate::model::input_rules::check_edge;
+    use crate::model::position::Position;
+
+    fn code<T, E: Into<InputError>>(result: Result<T, E>) -> Option<InputErrorCode> {
+        result.err().map(|error| error.into().code)
+    }
+
+    #[test]
+    fn cells_and_edges_should_be_on_the_board() {
+        let board = Board::new(3, 2);
+        assert_eq!(check_cell(&board, 1, 2), Ok(Position::new(1, 2)));
+        assert_eq!(
+            code(check_cell(&board, 2, 0)),
+            Some(InputErrorCode::CellOutsideBoard)
+        );
+        assert_eq!(
+            code(check_cell(&board, 0, -1)),
+            Some(InputErrorCode::CellOutsideBoard)
+        );
+
+        assert!(check_edge(&board, Position::new(0, 0), Position::new(1, 0)).is_ok());
+        assert_eq!(
+            code(check_edge(&board, Position::new(0, 0), Position::new(1, 1))),
+            Some(InputErrorCode::CellsNotAdjacent)
+        );
+        assert_eq!(
+            code(check_edge(&board, Position::new(0, 0), Position::new(0, 0))),
+            Some(InputErrorCode::CellsNotAdjacent)
+        );
+        assert_eq!(
+            code(check_edge(&board, Position::new(1, 2), Position::new(1, 3))),
+            Some(InputErrorCode::CellOutsideBoard)
+        );
+    }
+
+    #[test]
+    fn sizes_should_be_positive_and_bounded() {
+        assert_eq!(code(check_size(0)), Some(InputErrorCode::InvalidSize));
+        assert_eq!(check_size(10), Ok(10));
+        assert_eq!(code(check_size(1000)), Some(InputErrorCode::InvalidSize));
+    }
+
+    #[test]
+    fn errors_should_display_as_json_with_their_code() {
+        let error = InputError::new(InputErrorCode::UnknownCenter, "(1, 1) is not a center");
+        assert_eq!(
+            error.to_string(),
+            r#"{"code":"UnknownCenter","message":"(1, 1) is not a center"}"#
+        );
+    }
+}
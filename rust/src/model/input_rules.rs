@@ -0,0 +1,177 @@
+use crate::model::board::Board;
+use crate::model::border::Border;
+use crate::model::objective::Objective;
+use crate::model::position::Position;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// How strictly the moves of the player are checked before they're made
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Strictness {
+    /// Only moves that can't be made are refused, such as changing a locked wall
+    #[default]
+    Relaxed,
+    /// Moves that are certainly wrong are refused too, such as a wall through a center
+    Strict,
+}
+
+/// Why a move was refused
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MoveRejection {
+    /// The edge or the cell isn't on the board
+    OutsideBoard,
+    /// The two cells of the edge aren't next to each other
+    NotAdjacent,
+    /// The wall is given by the puzzle or was revealed by a hint
+    LockedWall,
+    /// The wall would run through a center
+    CutsCenter,
+    /// The edge has a wall, so it can't be marked as open
+    MarkerOnWall,
+    /// The position isn't one of the centers of the puzzle
+    NotACenter,
+    /// The mirror image of the move around the center is outside the board
+    MirrorOutsideBoard,
+    /// The move wouldn't change anything
+    NoChange,
+}
+
+impl Display for MoveRejection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let message = match self {
+            MoveRejection::OutsideBoard => "outside the board",
+            MoveRejection::NotAdjacent => "the cells are not adjacent",
+            MoveRejection::LockedWall => "the wall is locked",
+            MoveRejection::CutsCenter => "the wall would cut a center",
+            MoveRejection::MarkerOnWall => "the edge has a wall",
+            MoveRejection::NotACenter => "not a center",
+            MoveRejection::MirrorOutsideBoard => "the mirror image is outside the board",
+            MoveRejection::NoChange => "nothing would change",
+        };
+        write!(f, "{message}")
+    }
+}
+
+impl std::error::Error for MoveRejection {}
+
+/// Returns the edge between the two cells, if they're next to each other on the board
+pub fn check_edge(board: &Board, p1: Position, p2: Position) -> Result<Border, MoveRejection> {
+    if !board.contains(&p1) || !board.contains(&p2) {
+        return Err(MoveRejection::OutsideBoard);
+    }
+    if !p1.is_adjacent_to(&p2) {
+        return Err(MoveRejection::NotAdjacent);
+    }
+    Ok(Border::new(p1, p2))
+}
+
+/// Checks whether the wall on the edge of the board may be toggled. Locked walls can't be,
+/// and with [Strictness::Strict] neither can a wall be drawn through a center.
+pub fn check_wall(
+    board: &Board,
+    objective: &Objective,
+    border: &Border,
+    strictness: Strictness,
+) -> Result<(), MoveRejection> {
+    if !board.contains_border(border) {
+        return Err(MoveRejection::OutsideBoard);
+    }
+    if objective.walls.contains(border) {
+        return Err(MoveRejection::LockedWall);
+    }
+    let drawing = !board.is_active(border);
+    if strictness == Strictness::Strict
+        && drawing
+        && objective
+            .centers
+            .iter()
+            .any(|gc| cuts_center(border, &gc.position))
+    {
+        return Err(MoveRejection::CutsCenter);
+    }
+    Ok(())
+}
+
+/// Returns true iff a wall on the border separates cells sharing the center, given in
+/// half-steps, which is when the center is on the border or on one of its ends
+pub fn cuts_center(border: &Border, center: &Position) -> bool {
+    let cells = center.get_center_placement().get_positions();
+    cells.contains(&border.p1()) && cells.contains(&border.p2())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::board::Board;
+    use crate::model::border::Border;
+    use crate::model::galaxy::Galaxy;
+    use crate::model::input_rules::{
+        check_edge, check_wall, cuts_center, MoveRejection, Strictness,
+    };
+    use crate::model::objective::Objective;
+    use crate::model::position::Position;
+    use crate::model::universe::Universe;
+
+    fn border(r1: i32, c1: i32, r2: i32, c2: i32) -> Border {
+        Border::new(Position::new(r1, c1), Position::new(r2, c2))
+    }
+
+    #[test]
+    fn walls_should_cut_centers_on_them_or_their_ends() {
+        // The corner shared by the cells (0, 0), (0, 1), (1, 0) and (1, 1)
+        let corner = Position::new(1, 1);
+        assert!(cuts_center(&border(0, 0, 0, 1), &corner));
+        assert!(cuts_center(&border(0, 1, 1, 1), &corner));
+        assert!(!cuts_center(&border(1, 1, 1, 2), &corner));
+
+        // The edge between (0, 0) and (0, 1)
+        let edge = Position::new(0, 1);
+        assert!(cuts_center(&border(0, 0, 0, 1), &edge));
+        assert!(!cuts_center(&border(0, 0, 1, 0), &edge));
+
+        assert!(!cuts_center(&border(0, 0, 0, 1), &Position::new(0, 0)));
+    }
+
+    #[test]
+    fn strictness_should_decide_about_walls_through_centers() {
+        let galaxies = [Galaxy::from([(0, 0), (0, 1), (1, 0), (1, 1)])];
+        let objective = Objective::generate(&Universe::from(galaxies.as_slice()));
+        let mut board = Board::new(2, 2);
+        let wall = border(0, 0, 0, 1);
+
+        assert_eq!(
+            check_wall(&board, &objective, &wall, Strictness::Strict),
+            Err(MoveRejection::CutsCenter)
+        );
+        assert_eq!(
+            check_wall(&board, &objective, &wall, Strictness::Relaxed),
+            Ok(())
+        );
+
+        // Walls through centers can always be removed again
+        board.add_wall(wall.p1(), wall.p2());
+        assert_eq!(
+            check_wall(&board, &objective, &wall, Strictness::Strict),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn edges_should_be_between_adjacent_cells_on_the_board() {
+        let board = Board::new(2, 2);
+        assert_eq!(
+            check_edge(&board, Position::new(0, 1), Position::new(0, 2)),
+            Err(MoveRejection::OutsideBoard)
+        );
+        assert_eq!(
+            check_edge(&board, Position::new(0, 0), Position::new(1, 1)),
+            Err(MoveRejection::NotAdjacent)
+        );
+        assert_eq!(
+            check_edge(&board, Position::new(0, 1), Position::new(0, 0)),
+            Ok(border(0, 0, 0, 1))
+        );
+    }
+}
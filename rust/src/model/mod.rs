@@ -10,3 +10,19 @@ pub mod board_error;
 pub mod history;
 pub mod vec2;
 pub mod tree;
+pub mod puzzle;
+pub mod saved_game;
+pub mod share_code;
+pub mod compact;
+pub mod ownership;
+pub mod trial;
+pub mod input_rules;
+pub mod input_error;
+pub mod stats;
+pub mod hints;
+pub mod progress;
+pub mod solver;
+pub mod cursor;
+pub mod regions;
+#[cfg(test)]
+pub mod fixtures;
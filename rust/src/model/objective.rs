@@ -1,17 +1,17 @@
 use crate::model::border::Border;
 use crate::model::position::Position;
 use crate::model::universe::Universe;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use ts_rs::TS;
 
-#[derive(Debug, Eq, PartialEq, Copy, Clone, Ord, PartialOrd, Hash, Serialize, TS)]
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Ord, PartialOrd, Hash, Serialize, Deserialize, TS)]
 pub struct GalaxyCenter {
     pub position: Position,
     pub size: Option<usize>,
 }
 
-#[derive(Serialize, Clone, TS)]
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, TS)]
 pub struct Objective {
     pub centers: HashSet<GalaxyCenter>,
     pub walls: HashSet<Border>,
@@ -0,0 +1,192 @@
+use crate::model::board::Board;
+use crate::model::border::Border;
+use crate::model::galaxy::Galaxy;
+use crate::model::position::Position;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+
+/// Which center each cell has been painted into, if any. Centers are in half-steps.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Ownership {
+    owners: Vec<Vec<Option<Position>>>,
+}
+
+impl Ownership {
+    pub fn new(width: usize, height: usize) -> Self {
+        Ownership {
+            owners: vec![vec![None; width]; height],
+        }
+    }
+
+    pub fn get_width(&self) -> usize {
+        self.owners.first().map(Vec::len).unwrap_or(0)
+    }
+
+    pub fn get_height(&self) -> usize {
+        self.owners.len()
+    }
+
+    pub fn contains(&self, cell: &Position) -> bool {
+        cell.row >= 0
+            && cell.row < self.get_height() as i32
+            && cell.column >= 0
+            && cell.column < self.get_width() as i32
+    }
+
+    /// Returns true iff every row has the same width
+    pub fn is_rectangular(&self) -> bool {
+        self.owners.iter().all(|row| row.len() == self.get_width())
+    }
+
+    /// Returns the center the cell is painted into, if any
+    pub fn get_owner(&self, cell: &Position) -> Option<Position> {
+        if self.contains(cell) {
+            self.owners[cell.row as usize][cell.column as usize]
+        } else {
+            None
+        }
+    }
+
+    /// Paints the cell into the center, or clears it if the owner is None.
+    /// Returns the previous owner.
+    pub fn set_owner(&mut self, cell: &Position, owner: Option<Position>) -> Option<Position> {
+        debug_assert!(self.contains(cell));
+        std::mem::replace(
+            &mut self.owners[cell.row as usize][cell.column as usize],
+            owner,
+        )
+    }
+
+    /// Returns the painted cells with their owners, in reading order
+    pub fn get_painted_cells(&self) -> impl Iterator<Item = (Position, Position)> + '_ {
+        self.owners.iter().enumerate().flat_map(|(row, owners)| {
+            owners
+                .iter()
+                .enumerate()
+                .filter_map(move |(column, owner)| {
+                    owner.map(|owner| (Position::new(row as i32, column as i32), owner))
+                })
+        })
+    }
+
+    pub fn get_owners(&self) -> Vec<Vec<Option<Position>>> {
+        self.owners.clone()
+    }
+
+    /// Returns true iff there should be a wall between the two cells of the border,
+    /// which is when they have different owners. Two unpainted cells have the same owner.
+    pub fn is_boundary(&self, border: &Border) -> bool {
+        self.get_owner(&border.p1()) != self.get_owner(&border.p2())
+    }
+
+    /// Returns the cells reachable from the start without crossing a wall on the board,
+    /// through cells with the same owner as the start
+    pub fn get_fill_region(&self, board: &Board, start: &Position) -> BTreeSet<Position> {
+        let owner = self.get_owner(start);
+        let mut region = BTreeSet::new();
+        let mut queue = vec![*start];
+        while let Some(cell) = queue.pop() {
+            if !region.insert(cell) {
+                continue;
+            }
+            for neighbour in cell.adjacent() {
+                if board.contains(&neighbour)
+                    && !region.contains(&neighbour)
+                    && !board.is_wall(cell, neighbour)
+                    && self.get_owner(&neighbour) == owner
+                {
+                    queue.push(neighbour);
+                }
+            }
+        }
+        region
+    }
+
+    /// Returns the painted cells whose center isn't in the same galaxy on the board
+    pub fn get_misassigned_cells(&self, board: &Board) -> HashSet<Position> {
+        self.get_misassigned_cells_in(&board.get_galaxies())
+    }
+
+    /// Like [Ownership::get_misassigned_cells], only for the cells of the given galaxies
+    pub fn get_misassigned_cells_in(&self, galaxies: &[Galaxy]) -> HashSet<Position> {
+        let mut misassigned = HashSet::new();
+        for galaxy in galaxies {
+            for cell in galaxy.get_positions() {
+                if let Some(owner) = self.get_owner(cell) {
+                    let center_cells = owner.get_center_placement().get_positions();
+                    if !center_cells.iter().any(|p| galaxy.contains_position(p)) {
+                        misassigned.insert(*cell);
+                    }
+                }
+            }
+        }
+        misassigned
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::board::Board;
+    use crate::model::border::Border;
+    use crate::model::ownership::Ownership;
+    use crate::model::position::Position;
+    use std::collections::{BTreeSet, HashSet};
+
+    #[test]
+    fn boundaries_should_be_between_different_owners() {
+        let mut ownership = Ownership::new(3, 1);
+        ownership.set_owner(&Position::new(0, 0), Some(Position::new(0, 0)));
+        let left = Border::new(Position::new(0, 0), Position::new(0, 1));
+        let right = Border::new(Position::new(0, 1), Position::new(0, 2));
+        assert!(ownership.is_boundary(&left));
+        assert!(!ownership.is_boundary(&right));
+
+        ownership.set_owner(&Position::new(0, 1), Some(Position::new(0, 0)));
+        assert!(!ownership.is_boundary(&left));
+        assert!(ownership.is_boundary(&right));
+    }
+
+    #[test]
+    fn painted_cells_should_be_in_reading_order() {
+        let mut ownership = Ownership::new(2, 2);
+        ownership.set_owner(&Position::new(1, 0), Some(Position::new(2, 0)));
+        ownership.set_owner(&Position::new(0, 1), Some(Position::new(0, 2)));
+        assert_eq!(
+            ownership.get_painted_cells().collect::<Vec<_>>(),
+            vec![
+                (Position::new(0, 1), Position::new(0, 2)),
+                (Position::new(1, 0), Position::new(2, 0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn fill_region_should_stop_at_walls_and_other_owners() {
+        let mut board = Board::new(3, 2);
+        board.add_wall(Position::new(0, 0), Position::new(1, 0));
+        let mut ownership = Ownership::new(3, 2);
+        ownership.set_owner(&Position::new(0, 2), Some(Position::new(0, 4)));
+
+        let region = ownership.get_fill_region(&board, &Position::new(0, 0));
+
+        let expected: BTreeSet<Position> = [(0, 0), (0, 1), (1, 1), (1, 2), (1, 0)]
+            .into_iter()
+            .map(Position::from)
+            .collect();
+        assert_eq!(region, expected);
+    }
+
+    #[test]
+    fn cells_should_be_misassigned_when_their_center_is_walled_off() {
+        let mut board = Board::new(2, 1);
+        let mut ownership = Ownership::new(2, 1);
+        ownership.set_owner(&Position::new(0, 1), Some(Position::new(0, 0)));
+        assert!(ownership.get_misassigned_cells(&board).is_empty());
+
+        board.add_wall(Position::new(0, 0), Position::new(0, 1));
+        assert_eq!(
+            ownership.get_misassigned_cells(&board),
+            HashSet::from([Position::new(0, 1)])
+        );
+    }
+}
@@ -4,12 +4,12 @@ use crate::model::position::CenterPlacement::{
 };
 use crate::model::rectangle::Rectangle;
 use rand::Rng;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
 use std::ops::Sub;
 use ts_rs::TS;
 
-#[derive(Ord, PartialOrd, Eq, PartialEq, Debug, Copy, Clone, Hash, Serialize, TS)]
+#[derive(Ord, PartialOrd, Eq, PartialEq, Debug, Copy, Clone, Hash, Serialize, Deserialize, TS)]
 pub struct Position {
     pub row: i32,
     pub column: i32,
@@ -0,0 +1,155 @@
+use crate::model::board::Board;
+use crate::model::border::Border;
+use crate::model::hints::get_solution_walls;
+use crate::model::objective::Objective;
+use crate::model::universe::Universe;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// How the walls on an unfinished board compare with the solution
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ProgressReport {
+    /// Walls on the board that aren't in the solution, in order
+    pub wrong_walls: Vec<Border>,
+    /// The number of walls of the solution that are neither on the board nor given,
+    /// if it was asked for
+    pub missing_walls: Option<usize>,
+}
+
+impl ProgressReport {
+    /// Compares the walls on the board with the borders of the solution's galaxies.
+    /// Unlike [Board::compute_error], this works on unfinished boards.
+    pub fn compare(
+        board: &Board,
+        objective: &Objective,
+        solution: &Universe,
+        count_missing: bool,
+    ) -> ProgressReport {
+        let solution_walls = get_solution_walls(solution, board);
+        let mut wrong_walls: Vec<Border> = board
+            .get_borders()
+            .filter(|wall| !solution_walls.contains(wall))
+            .collect();
+        wrong_walls.sort();
+        let missing_walls = count_missing.then(|| {
+            solution_walls
+                .iter()
+                .filter(|wall| !board.is_active(wall) && !objective.walls.contains(wall))
+                .count()
+        });
+        ProgressReport {
+            wrong_walls,
+            missing_walls,
+        }
+    }
+
+    /// Returns true iff every wall on the board is in the solution
+    pub fn is_on_track(&self) -> bool {
+        self.wrong_walls.is_empty()
+    }
+}
+
+/// The walls on the board against the walls of the solution, each in order, so that the
+/// UI can animate the changes from one to the other
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct SolutionDiff {
+    /// Walls of the solution that are neither on the board nor given
+    pub walls_to_add: Vec<Border>,
+    /// Walls on the board that aren't in the solution
+    pub walls_to_remove: Vec<Border>,
+    /// Walls on the board that are in the solution
+    pub correct_walls: Vec<Border>,
+}
+
+impl SolutionDiff {
+    pub fn compare(board: &Board, objective: &Objective, solution: &Universe) -> SolutionDiff {
+        let solution_walls = get_solution_walls(solution, board);
+        let (mut correct_walls, mut walls_to_remove): (Vec<Border>, Vec<Border>) = board
+            .get_borders()
+            .partition(|wall| solution_walls.contains(wall));
+        correct_walls.sort();
+        walls_to_remove.sort();
+        let walls_to_add = solution_walls
+            .into_iter()
+            .filter(|wall| !board.is_active(wall) && !objective.walls.contains(wall))
+            .collect();
+        SolutionDiff {
+            walls_to_add,
+            walls_to_remove,
+            correct_walls,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::board::Board;
+    use crate::model::border::Border;
+    use crate::model::galaxy::Galaxy;
+    use crate::model::objective::Objective;
+    use crate::model::position::Position;
+    use crate::model::progress::{ProgressReport, SolutionDiff};
+    use crate::model::universe::Universe;
+
+    #[test]
+    fn should_report_wrong_and_missing_walls() {
+        let galaxies = [
+            Galaxy::from([(0, 0), (0, 1)]),
+            Galaxy::from([(1, 0)]),
+            Galaxy::from([(1, 1)]),
+        ];
+        let universe = Universe::from(galaxies.as_slice());
+        let mut objective = Objective::generate(&universe);
+        let given = Border::new(Position::new(0, 0), Position::new(1, 0));
+        objective.walls.insert(given);
+        let mut board = Board::new(2, 2);
+
+        let report = ProgressReport::compare(&board, &objective, &universe, true);
+        assert!(report.is_on_track());
+        assert_eq!(report.missing_walls, Some(2));
+
+        let wrong = Border::new(Position::new(0, 0), Position::new(0, 1));
+        board.add_wall(wrong.p1(), wrong.p2());
+        board.add_wall(Position::new(1, 0), Position::new(1, 1));
+        let report = ProgressReport::compare(&board, &objective, &universe, false);
+        assert_eq!(report.wrong_walls, vec![wrong]);
+        assert_eq!(report.missing_walls, None);
+        assert_eq!(
+            ProgressReport::compare(&board, &objective, &universe, true).missing_walls,
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn diff_should_split_the_walls_by_what_to_do_with_them() {
+        let galaxies = [
+            Galaxy::from([(0, 0), (0, 1)]),
+            Galaxy::from([(1, 0)]),
+            Galaxy::from([(1, 1)]),
+        ];
+        let universe = Universe::from(galaxies.as_slice());
+        let mut objective = Objective::generate(&universe);
+        objective
+            .walls
+            .insert(Border::new(Position::new(0, 0), Position::new(1, 0)));
+        let mut board = Board::new(2, 2);
+        board.add_wall(Position::new(0, 0), Position::new(0, 1));
+        board.add_wall(Position::new(1, 0), Position::new(1, 1));
+
+        let diff = SolutionDiff::compare(&board, &objective, &universe);
+        assert_eq!(
+            diff.walls_to_add,
+            vec![Border::new(Position::new(0, 1), Position::new(1, 1))]
+        );
+        assert_eq!(
+            diff.walls_to_remove,
+            vec![Border::new(Position::new(0, 0), Position::new(0, 1))]
+        );
+        assert_eq!(
+            diff.correct_walls,
+            vec![Border::new(Position::new(1, 0), Position::new(1, 1))]
+        );
+    }
+}
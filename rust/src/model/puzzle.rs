@@ -0,0 +1,446 @@
+use crate::model::border::Border;
+use crate::model::objective::Objective;
+use crate::model::position::Position;
+use crate::model::universe::Universe;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use ts_rs::TS;
+
+/// The version of the puzzle document written by [Puzzle::to_json]
+pub const CURRENT_VERSION: u32 = 1;
+
+/// Upgrades a puzzle document from one version to the next
+type Migration = fn(Value) -> Result<Value, PuzzleError>;
+
+/// Migrations indexed by the version they upgrade from, i.e. `MIGRATIONS[0]` upgrades
+/// a version 1 document to version 2. When the document format changes, bump
+/// [CURRENT_VERSION] and append a migration here so stored puzzles keep loading.
+const MIGRATIONS: &[Migration] = &[];
+
+/// A self-contained puzzle: the board size, what the player sees, and the solution
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Puzzle {
+    pub version: u32,
+    pub width: usize,
+    pub height: usize,
+    pub objective: Objective,
+    pub solution: Universe,
+    #[serde(default)]
+    pub metadata: PuzzleMetadata,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+#[serde(default)]
+pub struct PuzzleMetadata {
+    pub id: Option<String>,
+    pub title: Option<String>,
+    pub author: Option<String>,
+    pub difficulty: Option<Difficulty>,
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, TS)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    /// Estimates how hard the puzzle is from how many cells each center has to
+    /// claim on average. Given walls and sizes are clues and make it easier.
+    pub fn estimate(solution: &Universe, objective: &Objective) -> Difficulty {
+        let centers = objective.centers.len().max(1) as f64;
+        let cells = (solution.get_width() * solution.get_height()) as f64;
+        let clues = objective.walls.len()
+            + objective
+                .centers
+                .iter()
+                .filter(|gc| gc.size.is_some())
+                .count();
+        let cells_per_center = (cells - clues as f64).max(0.0) / centers;
+        if cells_per_center < 3.0 {
+            Difficulty::Easy
+        } else if cells_per_center < 4.0 {
+            Difficulty::Medium
+        } else {
+            Difficulty::Hard
+        }
+    }
+}
+
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Difficulty::Easy => write!(f, "Easy"),
+            Difficulty::Medium => write!(f, "Medium"),
+            Difficulty::Hard => write!(f, "Hard"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum PuzzleError {
+    /// The document isn't valid json, or doesn't have the shape of a puzzle
+    Malformed(String),
+    /// The document has no version field
+    MissingVersion,
+    /// The document was written by a version we don't know how to read
+    UnsupportedVersion(u32),
+    /// The solution isn't a rectangular grid of valid galaxies of the puzzle's size
+    InvalidSolution,
+    /// A center lies outside the board
+    CenterOutOfBounds(Position),
+    /// A given wall lies outside the board
+    WallOutOfBounds(Border),
+    /// The centers don't match the galaxies of the solution
+    CentersDoNotMatchSolution,
+    /// A given wall isn't part of the solution
+    WallNotInSolution(Border),
+}
+
+impl Display for PuzzleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PuzzleError::Malformed(message) => write!(f, "malformed puzzle: {message}"),
+            PuzzleError::MissingVersion => write!(f, "puzzle has no version"),
+            PuzzleError::UnsupportedVersion(version) => {
+                write!(f, "unsupported puzzle version {version}")
+            }
+            PuzzleError::InvalidSolution => write!(f, "puzzle solution is invalid"),
+            PuzzleError::CenterOutOfBounds(center) => {
+                write!(f, "center {center} is outside the board")
+            }
+            PuzzleError::WallOutOfBounds(wall) => {
+                write!(f, "wall {}-{} is outside the board", wall.p1(), wall.p2())
+            }
+            PuzzleError::CentersDoNotMatchSolution => {
+                write!(f, "centers do not match the solution")
+            }
+            PuzzleError::WallNotInSolution(wall) => {
+                write!(
+                    f,
+                    "wall {}-{} is not part of the solution",
+                    wall.p1(),
+                    wall.p2()
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for PuzzleError {}
+
+impl From<serde_json::Error> for PuzzleError {
+    fn from(error: serde_json::Error) -> Self {
+        PuzzleError::Malformed(error.to_string())
+    }
+}
+
+impl Puzzle {
+    pub fn new(solution: Universe, objective: Objective) -> Self {
+        let difficulty = Difficulty::estimate(&solution, &objective);
+        Puzzle {
+            version: CURRENT_VERSION,
+            width: solution.get_width(),
+            height: solution.get_height(),
+            objective,
+            solution,
+            metadata: PuzzleMetadata {
+                difficulty: Some(difficulty),
+                ..PuzzleMetadata::default()
+            },
+        }
+    }
+
+    pub fn generate(width: usize, height: usize) -> Self {
+        let solution = Universe::generate(width, height);
+        let objective = Objective::generate(&solution);
+        Puzzle::new(solution, objective)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("puzzles are always serializable")
+    }
+
+    /// Reads a puzzle document of any known version, migrating it to the
+    /// current version and validating it
+    pub fn from_json(json: &str) -> Result<Puzzle, PuzzleError> {
+        Puzzle::from_value(serde_json::from_str(json)?)
+    }
+
+    /// Like [Puzzle::from_json], for documents embedded in other documents
+    pub fn from_value(document: Value) -> Result<Puzzle, PuzzleError> {
+        let document = migrate(document, MIGRATIONS)?;
+        let puzzle: Puzzle = serde_json::from_value(document)?;
+        puzzle.validate()?;
+        Ok(puzzle)
+    }
+
+    /// Returns true iff the given half-step position lies on the board
+    pub fn contains_center(&self, center: &Position) -> bool {
+        center.row >= 0
+            && center.row <= 2 * (self.height as i32 - 1)
+            && center.column >= 0
+            && center.column <= 2 * (self.width as i32 - 1)
+    }
+
+    fn contains(&self, position: &Position) -> bool {
+        position.row >= 0
+            && position.row < self.height as i32
+            && position.column >= 0
+            && position.column < self.width as i32
+    }
+
+    /// Checks that the solution is a valid universe of the right size,
+    /// and that the objective is consistent with it
+    pub fn validate(&self) -> Result<(), PuzzleError> {
+        let solution = &self.solution;
+        if !solution.is_well_formed()
+            || solution.get_width() != self.width
+            || solution.get_height() != self.height
+            || !solution.is_valid()
+        {
+            return Err(PuzzleError::InvalidSolution);
+        }
+
+        if let Some(gc) = self
+            .objective
+            .centers
+            .iter()
+            .find(|gc| !self.contains_center(&gc.position))
+        {
+            return Err(PuzzleError::CenterOutOfBounds(gc.position));
+        }
+
+        let galaxies = solution.get_galaxies();
+        let solution_centers: HashSet<Position> = galaxies.iter().map(|g| g.center()).collect();
+        let objective_centers: HashSet<Position> = self
+            .objective
+            .centers
+            .iter()
+            .map(|gc| gc.position)
+            .collect();
+        if solution_centers != objective_centers || self.objective.centers.len() != galaxies.len() {
+            return Err(PuzzleError::CentersDoNotMatchSolution);
+        }
+        let sizes_match = self.objective.centers.iter().all(|gc| {
+            gc.size.is_none_or(|size| {
+                galaxies
+                    .iter()
+                    .any(|g| g.center() == gc.position && g.size() == size)
+            })
+        });
+        if !sizes_match {
+            return Err(PuzzleError::CentersDoNotMatchSolution);
+        }
+
+        for wall in &self.objective.walls {
+            if !self.contains(&wall.p1()) || !self.contains(&wall.p2()) {
+                return Err(PuzzleError::WallOutOfBounds(*wall));
+            }
+            if solution.are_neighbours(&wall.p1(), &wall.p2()) {
+                return Err(PuzzleError::WallNotInSolution(*wall));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Runs the migrations necessary to bring the document up to the version
+/// following the last migration
+fn migrate(mut document: Value, migrations: &[Migration]) -> Result<Value, PuzzleError> {
+    let latest_version = migrations.len() as u32 + 1;
+    let mut version = read_version(&document)?;
+    if version == 0 || version > latest_version {
+        return Err(PuzzleError::UnsupportedVersion(version));
+    }
+    while version < latest_version {
+        document = migrations[version as usize - 1](document)?;
+        version += 1;
+        if let Some(object) = document.as_object_mut() {
+            object.insert("version".to_string(), Value::from(version));
+        }
+    }
+    Ok(document)
+}
+
+fn read_version(document: &Value) -> Result<u32, PuzzleError> {
+    let version = document.get("version").ok_or(PuzzleError::MissingVersion)?;
+    version
+        .as_u64()
+        .and_then(|version| u32::try_from(version).ok())
+        .ok_or_else(|| PuzzleError::Malformed(format!("invalid version {version}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::border::Border;
+    use crate::model::galaxy::Galaxy;
+    use crate::model::objective::Objective;
+    use crate::model::position::Position;
+    use crate::model::puzzle::{
+        migrate, Difficulty, Migration, Puzzle, PuzzleError, CURRENT_VERSION, MIGRATIONS,
+    };
+    use crate::model::universe::Universe;
+    use serde_json::{json, Value};
+
+    fn small_puzzle() -> Puzzle {
+        let galaxies = [
+            Galaxy::from([(0, 0), (0, 1)]),
+            Galaxy::from([(1, 0)]),
+            Galaxy::from([(1, 1)]),
+        ];
+        let solution = Universe::from(galaxies.as_slice());
+        let objective = Objective::generate(&solution);
+        Puzzle::new(solution, objective)
+    }
+
+    #[test]
+    fn current_version_should_follow_the_last_migration() {
+        assert_eq!(CURRENT_VERSION, MIGRATIONS.len() as u32 + 1);
+    }
+
+    #[test]
+    fn should_round_trip_through_json() {
+        let mut puzzle = small_puzzle();
+        puzzle.metadata.title = Some("Small".to_string());
+        puzzle
+            .objective
+            .walls
+            .insert(Border::new(Position::new(0, 0), Position::new(1, 0)));
+
+        let read = Puzzle::from_json(&puzzle.to_json()).unwrap();
+
+        assert_eq!(read.version, CURRENT_VERSION);
+        assert_eq!(read.width, 2);
+        assert_eq!(read.height, 2);
+        assert_eq!(read.objective.centers, puzzle.objective.centers);
+        assert_eq!(read.objective.walls, puzzle.objective.walls);
+        assert_eq!(read.solution.to_string(), puzzle.solution.to_string());
+        assert_eq!(read.metadata, puzzle.metadata);
+    }
+
+    #[test]
+    fn should_round_trip_generated_puzzle() {
+        let puzzle = Puzzle::generate(5, 4);
+        let read = Puzzle::from_json(&puzzle.to_json()).unwrap();
+        assert_eq!(read.solution.to_string(), puzzle.solution.to_string());
+    }
+
+    #[test]
+    fn should_reject_missing_version() {
+        let mut document: Value = serde_json::from_str(&small_puzzle().to_json()).unwrap();
+        document.as_object_mut().unwrap().remove("version");
+        assert_eq!(
+            Puzzle::from_json(&document.to_string()).unwrap_err(),
+            PuzzleError::MissingVersion
+        );
+    }
+
+    #[test]
+    fn should_reject_future_version() {
+        let mut document: Value = serde_json::from_str(&small_puzzle().to_json()).unwrap();
+        document["version"] = json!(CURRENT_VERSION + 1);
+        assert_eq!(
+            Puzzle::from_json(&document.to_string()).unwrap_err(),
+            PuzzleError::UnsupportedVersion(CURRENT_VERSION + 1)
+        );
+    }
+
+    #[test]
+    fn should_reject_non_adjacent_walls() {
+        let mut document: Value = serde_json::from_str(&small_puzzle().to_json()).unwrap();
+        document["objective"]["walls"] = json!([{
+            "p1": { "row": 0, "column": 0 },
+            "p2": { "row": 1, "column": 1 },
+        }]);
+        assert!(matches!(
+            Puzzle::from_json(&document.to_string()),
+            Err(PuzzleError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn should_reject_centers_that_do_not_match_the_solution() {
+        let mut document: Value = serde_json::from_str(&small_puzzle().to_json()).unwrap();
+        document["objective"]["centers"] = json!([{
+            "position": { "row": 0, "column": 0 },
+            "size": null,
+        }]);
+        assert_eq!(
+            Puzzle::from_json(&document.to_string()).unwrap_err(),
+            PuzzleError::CentersDoNotMatchSolution
+        );
+    }
+
+    #[test]
+    fn should_reject_walls_inside_galaxies() {
+        let mut puzzle = small_puzzle();
+        puzzle
+            .objective
+            .walls
+            .insert(Border::new(Position::new(0, 0), Position::new(0, 1)));
+        assert!(matches!(
+            Puzzle::from_json(&puzzle.to_json()),
+            Err(PuzzleError::WallNotInSolution(_))
+        ));
+    }
+
+    #[test]
+    fn should_run_migrations_in_order() {
+        let migrations: &[Migration] = &[
+            |mut document| {
+                document["steps"] = json!(["1 to 2"]);
+                Ok(document)
+            },
+            |mut document| {
+                document["steps"]
+                    .as_array_mut()
+                    .unwrap()
+                    .push(json!("2 to 3"));
+                Ok(document)
+            },
+        ];
+        let migrated = migrate(json!({ "version": 1 }), migrations).unwrap();
+        assert_eq!(
+            migrated,
+            json!({ "version": 3, "steps": ["1 to 2", "2 to 3"] })
+        );
+        let migrated = migrate(json!({ "version": 2, "steps": [] }), migrations).unwrap();
+        assert_eq!(migrated, json!({ "version": 3, "steps": ["2 to 3"] }));
+    }
+
+    #[test]
+    fn difficulty_should_grow_with_galaxy_size_and_shrink_with_clues() {
+        let puzzle = small_puzzle();
+        assert_eq!(puzzle.metadata.difficulty, Some(Difficulty::Easy));
+
+        let galaxies = [
+            Galaxy::from([(0, 0), (0, 1), (0, 2), (0, 3), (0, 4)]),
+            Galaxy::from([(1, 0), (1, 1), (1, 2), (1, 3), (1, 4)]),
+        ];
+        let solution = Universe::from(galaxies.as_slice());
+        let mut objective = Objective::generate(&solution);
+        assert_eq!(
+            Difficulty::estimate(&solution, &objective),
+            Difficulty::Hard
+        );
+        objective
+            .walls
+            .insert(Border::new(Position::new(0, 0), Position::new(1, 0)));
+        objective
+            .walls
+            .insert(Border::new(Position::new(0, 4), Position::new(1, 4)));
+        objective
+            .walls
+            .insert(Border::new(Position::new(0, 2), Position::new(1, 2)));
+        assert_eq!(
+            Difficulty::estimate(&solution, &objective),
+            Difficulty::Medium
+        );
+    }
+}
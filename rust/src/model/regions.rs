@@ -0,0 +1,107 @@
+use crate::model::board::Board;
+use crate::model::galaxy::Galaxy;
+use crate::model::objective::Objective;
+use crate::model::position::Position;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// A region of the board enclosed by walls
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct Region {
+    /// The center of the objective in the region, if there is exactly one
+    pub center: Option<Position>,
+    /// Whether the region is a finished galaxy: symmetric around its only center, of the
+    /// right size and without walls inside. Otherwise it's still in progress.
+    pub complete: bool,
+}
+
+/// The regions of the board, for filling finished galaxies
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct RegionMap {
+    /// The index of the region of each cell in [RegionMap::regions], by row
+    pub ids: Vec<Vec<usize>>,
+    pub regions: Vec<Region>,
+}
+
+impl RegionMap {
+    pub fn new(board: &Board, objective: &Objective) -> RegionMap {
+        let mut ids = vec![vec![0; board.get_width()]; board.get_height()];
+        let galaxies = board.get_galaxies();
+        for (id, galaxy) in galaxies.iter().enumerate() {
+            for cell in galaxy.get_positions() {
+                ids[cell.row as usize][cell.column as usize] = id;
+            }
+        }
+        let regions = galaxies
+            .iter()
+            .map(|galaxy| RegionMap::get_region(board, objective, galaxy))
+            .collect();
+        RegionMap { ids, regions }
+    }
+
+    fn get_region(board: &Board, objective: &Objective, galaxy: &Galaxy) -> Region {
+        let mut centers = objective.centers.iter().filter(|center| {
+            center
+                .position
+                .get_center_placement()
+                .get_positions()
+                .iter()
+                .any(|cell| galaxy.contains_position(cell))
+        });
+        let center = match (centers.next(), centers.next()) {
+            (Some(center), None) => Some(center),
+            _ => None,
+        };
+        let has_inner_walls = board.get_borders().any(|wall| {
+            galaxy.contains_position(&wall.p1()) && galaxy.contains_position(&wall.p2())
+        });
+        let complete = center.is_some_and(|center| {
+            galaxy.center() == center.position
+                && galaxy.is_valid()
+                && center.size.is_none_or(|size| galaxy.size() == size)
+                && !has_inner_walls
+        });
+        Region {
+            center: center.map(|center| center.position),
+            complete,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::board::Board;
+    use crate::model::fixtures;
+    use crate::model::objective::Objective;
+    use crate::model::position::Position;
+    use crate::model::regions::RegionMap;
+
+    fn objective() -> Objective {
+        Objective::generate(&fixtures::small_universe())
+    }
+
+    #[test]
+    fn only_finished_galaxies_should_be_complete() {
+        let objective = objective();
+        let mut board = Board::new(3, 2);
+        board.add_wall(Position::new(0, 1), Position::new(0, 2));
+        board.add_wall(Position::new(1, 1), Position::new(1, 2));
+
+        let map = RegionMap::new(&board, &objective);
+        assert_eq!(map.ids, vec![vec![0, 0, 1], vec![0, 0, 1]]);
+        assert!(map.regions[0].complete);
+        assert_eq!(map.regions[0].center, Some(Position::new(1, 1)));
+        assert!(!map.regions[1].complete);
+        assert_eq!(map.regions[1].center, None);
+
+        board.add_wall(Position::new(0, 2), Position::new(1, 2));
+        board.add_wall(Position::new(0, 0), Position::new(0, 1));
+        let map = RegionMap::new(&board, &objective);
+        assert_eq!(map.regions.len(), 3);
+        assert!(!map.regions[0].complete);
+        assert!(map.regions[1].complete);
+        assert!(map.regions[2].complete);
+    }
+}
@@ -0,0 +1,384 @@
+use crate::model::board::Board;
+use crate::model::compact::{CompactError, Edges, Reader};
+use crate::model::history::{HintUndo, History};
+use crate::model::input_rules::Strictness;
+use crate::model::ownership::Ownership;
+use crate::model::puzzle::{Puzzle, PuzzleError};
+use crate::model::stats::{CompletionSummary, Stats, Timer};
+use crate::model::trial::Trial;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt::{Display, Formatter};
+
+/// The version of the saved game format written by [SavedGame::encode]
+pub const SAVE_VERSION: u32 = 1;
+
+/// Everything needed to resume a game exactly where the player left it
+#[derive(Serialize, Deserialize)]
+pub struct SavedGame {
+    pub version: u32,
+    /// The objective and the hidden solution
+    pub puzzle: Puzzle,
+    /// The walls and markers the player has drawn. Like the history, the board is written
+    /// in a compact format instead, see [SavedGame::encode].
+    #[serde(skip_serializing)]
+    pub board: Board,
+    #[serde(skip_serializing)]
+    pub history: History,
+    pub hints_used: usize,
+    #[serde(default)]
+    pub hint_undo: HintUndo,
+    /// The cells painted into centers, missing in games saved before painting existed
+    #[serde(default)]
+    pub ownership: Option<Ownership>,
+    #[serde(default)]
+    pub mirror_assist: bool,
+    #[serde(default)]
+    pub live_errors: bool,
+    /// The trials in progress, innermost last
+    #[serde(default)]
+    pub trials: Vec<Trial>,
+    #[serde(default)]
+    pub strictness: Strictness,
+    /// The timer is always saved paused, the host resumes it
+    #[serde(default)]
+    pub timer: Timer,
+    #[serde(default)]
+    pub stats: Stats,
+    #[serde(default)]
+    pub completion: Option<CompletionSummary>,
+    /// Whether the player had checked the current board, in which case the errors are shown
+    pub checked: bool,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum SaveError {
+    /// The string isn't a saved game
+    Malformed(String),
+    /// The game was saved by a version we don't know how to read
+    UnsupportedVersion(u32),
+    /// The puzzle inside the saved game is invalid
+    InvalidPuzzle(PuzzleError),
+    /// The board has a different size than the puzzle, or walls outside of it
+    BoardDoesNotMatchPuzzle,
+    /// The history or the trials refer to walls or cells outside the board, or to nodes
+    /// that aren't in the history
+    InvalidHistory,
+    /// The painted cells have a different size than the board
+    InvalidOwnership,
+}
+
+impl Display for SaveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SaveError::Malformed(message) => write!(f, "malformed saved game: {message}"),
+            SaveError::UnsupportedVersion(version) => {
+                write!(f, "unsupported saved game version {version}")
+            }
+            SaveError::InvalidPuzzle(error) => write!(f, "invalid saved puzzle: {error}"),
+            SaveError::BoardDoesNotMatchPuzzle => write!(f, "saved board does not match puzzle"),
+            SaveError::InvalidHistory => write!(f, "saved history is invalid"),
+            SaveError::InvalidOwnership => write!(f, "saved painted cells are invalid"),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+impl From<serde_json::Error> for SaveError {
+    fn from(error: serde_json::Error) -> Self {
+        SaveError::Malformed(error.to_string())
+    }
+}
+
+impl From<PuzzleError> for SaveError {
+    fn from(error: PuzzleError) -> Self {
+        SaveError::InvalidPuzzle(error)
+    }
+}
+
+impl SavedGame {
+    /// Writes the saved game as JSON without whitespace, except for the board and the
+    /// history, which grow with every move. Those are url-safe base64 strings in the compact
+    /// format of share codes, with walls as edge indices and numbers as LEB128 varints:
+    /// the board is the player's walls followed by the markers, and the history is written
+    /// by [History::write_compact]. Drawing a wall adds about 6 characters, so the host can
+    /// save after every move.
+    pub fn encode(&self) -> String {
+        let mut document = serde_json::to_value(self).expect("saved games are always serializable");
+        let edges = Edges::new(self.board.get_width(), self.board.get_height());
+        let mut board = Vec::new();
+        edges.write_walls(&mut board, &self.board.get_borders().collect());
+        edges.write_walls(&mut board, &self.board.get_markers().collect());
+        let mut history = Vec::new();
+        self.history.write_compact(&mut history, &edges);
+        document["board"] = Value::String(URL_SAFE_NO_PAD.encode(board));
+        document["history"] = Value::String(URL_SAFE_NO_PAD.encode(history));
+        document.to_string()
+    }
+
+    /// Reads a saved game, checking that the puzzle, board and history fit together
+    pub fn decode(string: &str) -> Result<SavedGame, SaveError> {
+        let mut document: Value = serde_json::from_str(string)?;
+        let version = document
+            .get("version")
+            .and_then(Value::as_u64)
+            .ok_or_else(|| SaveError::Malformed("missing version".to_string()))?;
+        if version != SAVE_VERSION as u64 {
+            return Err(SaveError::UnsupportedVersion(version as u32));
+        }
+
+        // The puzzle has its own version and migrations, so it's read separately
+        let puzzle = document
+            .get_mut("puzzle")
+            .map(Value::take)
+            .ok_or_else(|| SaveError::Malformed("missing puzzle".to_string()))?;
+        let puzzle = Puzzle::from_value(puzzle)?;
+        document["puzzle"] = serde_json::to_value(&puzzle)?;
+
+        // The board and the history number the edges of the puzzle's board
+        if puzzle.width == 0 || puzzle.height == 0 {
+            return Err(PuzzleError::InvalidSolution.into());
+        }
+        let edges = Edges::new(puzzle.width, puzzle.height);
+        let bytes = take_compact(&mut document, "board")?;
+        let mut reader = Reader::new(&bytes);
+        let board_error = |error| from_compact(error, SaveError::BoardDoesNotMatchPuzzle);
+        let walls = edges.read_walls(&mut reader).map_err(board_error)?;
+        let markers = edges.read_walls(&mut reader).map_err(board_error)?;
+        if !reader.is_empty() {
+            return Err(SaveError::Malformed("board has trailing data".to_string()));
+        }
+        let mut board = Board::with_walls(puzzle.width, puzzle.height, walls);
+        for marker in &markers {
+            board.toggle_marker(marker);
+        }
+        document["board"] = serde_json::to_value(&board)?;
+
+        let bytes = take_compact(&mut document, "history")?;
+        let mut reader = Reader::new(&bytes);
+        let history = History::read_compact(&mut reader, &edges)
+            .map_err(|error| from_compact(error, SaveError::InvalidHistory))?;
+        if !reader.is_empty() {
+            return Err(SaveError::Malformed(
+                "history has trailing data".to_string(),
+            ));
+        }
+        document["history"] = serde_json::to_value(&history)?;
+
+        let saved_game: SavedGame = serde_json::from_value(document)?;
+        saved_game.validate()?;
+        Ok(saved_game)
+    }
+
+    fn validate(&self) -> Result<(), SaveError> {
+        let board = &self.board;
+        if board.get_width() != self.puzzle.width
+            || board.get_height() != self.puzzle.height
+            || !board
+                .get_borders()
+                .chain(board.get_markers())
+                .all(|border| board.contains_border(&border))
+        {
+            return Err(SaveError::BoardDoesNotMatchPuzzle);
+        }
+
+        let history_within_board = self.history.get_entries().all(|entry| {
+            entry
+                .get_borders()
+                .iter()
+                .all(|border| board.contains_border(border))
+                && entry.get_cells().iter().all(|cell| board.contains(cell))
+        });
+        let trials_within_history = self.trials.iter().all(|trial| {
+            self.history.contains(trial.checkpoint)
+                && trial.walls.iter().all(|wall| board.contains_border(wall))
+        });
+        if !self.history.is_consistent() || !history_within_board || !trials_within_history {
+            return Err(SaveError::InvalidHistory);
+        }
+
+        if let Some(ownership) = &self.ownership {
+            if !ownership.is_rectangular()
+                || ownership.get_width() != board.get_width()
+                || ownership.get_height() != board.get_height()
+            {
+                return Err(SaveError::InvalidOwnership);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Takes the bytes of a field written in the compact format out of the document
+fn take_compact(document: &mut Value, field: &str) -> Result<Vec<u8>, SaveError> {
+    let string = document
+        .get_mut(field)
+        .map(Value::take)
+        .ok_or_else(|| SaveError::Malformed(format!("missing {field}")))?;
+    string
+        .as_str()
+        .and_then(|string| URL_SAFE_NO_PAD.decode(string).ok())
+        .ok_or_else(|| SaveError::Malformed(format!("{field} is not url-safe base64")))
+}
+
+/// Reports walls outside the board as the given error, and anything else as malformed
+fn from_compact(error: CompactError, out_of_bounds: SaveError) -> SaveError {
+    match error {
+        CompactError::WallOutOfBounds(_) => out_of_bounds,
+        error => SaveError::Malformed(error.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::border::Border;
+    use crate::model::compact::Edges;
+    use crate::model::galaxy::Galaxy;
+    use crate::model::game_state::GameState;
+    use crate::model::input_rules::MoveRejection;
+    use crate::model::position::Position;
+    use crate::model::saved_game::{SaveError, SavedGame};
+    use crate::model::universe::Universe;
+    use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    use base64::Engine;
+    use serde_json::{json, Value};
+    use std::collections::HashSet;
+
+    /// The wall between the two galaxies of [played_game] that the hint reveals
+    fn hint_wall() -> Border {
+        Border::new(Position::new(1, 3), Position::new(2, 3))
+    }
+
+    /// A 4 by 4 game split into two 4 by 2 galaxies, where the player drew all walls but
+    /// one, so that the hint reveals the last, then drew a wrong wall and took it back
+    fn played_game() -> GameState {
+        let galaxies = [
+            Galaxy::from((0..2).flat_map(|row| (0..4).map(move |column| (row, column)))),
+            Galaxy::from((2..4).flat_map(|row| (0..4).map(move |column| (row, column)))),
+        ];
+        let mut state = GameState::new(Universe::from(galaxies.as_slice()));
+        state.toggle_border(1, 0, 2, 0).unwrap();
+        state.toggle_border(1, 1, 2, 1).unwrap();
+        state.toggle_border(1, 2, 2, 2).unwrap();
+        state.take_hint();
+        state.toggle_border(0, 0, 0, 1).unwrap();
+        state.undo();
+        state.check_solution();
+        state
+    }
+
+    fn walls(state: &GameState) -> Vec<Border> {
+        let mut walls: Vec<Border> = state.board.get_borders().collect();
+        walls.sort();
+        walls
+    }
+
+    #[test]
+    fn should_resume_exactly() {
+        let state = played_game();
+        assert!(state.board.is_active(&hint_wall()));
+        let mut resumed = GameState::import(&state.export()).unwrap();
+
+        assert_eq!(walls(&resumed), walls(&state));
+        assert_eq!(resumed.objective.centers, state.objective.centers);
+        assert_eq!(resumed.objective.walls, HashSet::from([hint_wall()]));
+        assert_eq!(resumed.universe.to_string(), state.universe.to_string());
+        assert_eq!(resumed.hints_used, 1);
+        assert!(resumed.error.is_some());
+        assert!(resumed.history.has_future());
+        let (p1, p2) = hint_wall().into();
+        assert_eq!(
+            resumed.toggle_border(p1.row, p1.column, p2.row, p2.column),
+            Err(MoveRejection::LockedWall)
+        );
+
+        resumed.redo();
+        assert!(resumed
+            .board
+            .is_wall(Position::new(0, 0), Position::new(0, 1)));
+        resumed.undo();
+        resumed.undo();
+        assert!(!resumed.board.is_active(&hint_wall()));
+        assert!(resumed.objective.walls.is_empty());
+        assert!(resumed
+            .board
+            .is_wall(Position::new(1, 2), Position::new(2, 2)));
+
+        resumed.redo();
+        assert!(resumed.board.is_active(&hint_wall()));
+        assert_eq!(resumed.objective.walls, HashSet::from([hint_wall()]));
+    }
+
+    #[test]
+    fn export_should_grow_slowly_with_the_moves() {
+        let dominoes: Vec<Galaxy> = (0..10)
+            .flat_map(|row| {
+                (0..5).map(move |pair| Galaxy::from([(row, 2 * pair), (row, 2 * pair + 1)]))
+            })
+            .collect();
+        let mut state = GameState::new(Universe::from(dominoes.as_slice()));
+        let start = state.export().len();
+        assert!(start < 8 * 1024, "a new game takes {start} bytes");
+
+        for row in 0..9 {
+            for column in 0..10 {
+                state.toggle_border(row, column, row + 1, column).unwrap();
+            }
+        }
+        let (moves, played) = (90, state.export().len());
+        assert!(
+            played - start < 6 * moves,
+            "{moves} moves take {} bytes",
+            played - start
+        );
+    }
+
+    #[test]
+    fn should_reject_garbage() {
+        assert!(matches!(
+            GameState::import("not a saved game"),
+            Err(SaveError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn should_reject_unknown_version() {
+        let mut document: Value = serde_json::from_str(&played_game().export()).unwrap();
+        document["version"] = json!(99);
+        assert_eq!(
+            SavedGame::decode(&document.to_string()).err(),
+            Some(SaveError::UnsupportedVersion(99))
+        );
+    }
+
+    #[test]
+    fn should_reject_walls_outside_the_board() {
+        let mut document: Value = serde_json::from_str(&played_game().export()).unwrap();
+        // Numbered on a larger board, the wall lies below the 4 by 4 board of the game
+        let edges = Edges::new(10, 10);
+        let wall = Border::new(Position::new(9, 0), Position::new(9, 1));
+        let mut bytes = Vec::new();
+        edges.write_walls(&mut bytes, &HashSet::from([wall]));
+        edges.write_walls(&mut bytes, &HashSet::new());
+        document["board"] = json!(URL_SAFE_NO_PAD.encode(bytes));
+        assert_eq!(
+            SavedGame::decode(&document.to_string()).err(),
+            Some(SaveError::BoardDoesNotMatchPuzzle)
+        );
+    }
+
+    #[test]
+    fn should_reject_history_out_of_range() {
+        let mut document: Value = serde_json::from_str(&played_game().export()).unwrap();
+        // No moves, tree policy, but the current node is 100
+        document["history"] = json!(URL_SAFE_NO_PAD.encode([0, 100, 0]));
+        assert_eq!(
+            SavedGame::decode(&document.to_string()).err(),
+            Some(SaveError::InvalidHistory)
+        );
+    }
+}
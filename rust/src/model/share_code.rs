@@ -0,0 +1,403 @@
+use crate::model::board::Board;
+use crate::model::border::Border;
+use crate::model::compact::{write_varint, CompactError, Edges, Reader};
+use crate::model::objective::{GalaxyCenter, Objective};
+use crate::model::position::Position;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+
+/// The version of the share code format written by [SharedPuzzle::encode]
+const SHARE_CODE_VERSION: u8 = 1;
+
+/// Boards larger than this can't be encoded, since half-step coordinates are stored in a byte
+pub const MAX_SHARE_CODE_SIZE: usize = 128;
+
+const KIND_PUZZLE: u8 = 0;
+const KIND_PUZZLE_WITH_PROGRESS: u8 = 1;
+
+/// A puzzle as it can be shared in chat and urls: the size of the board, the centers
+/// and the given walls, and optionally the walls the player has drawn so far.
+/// It doesn't contain the solution.
+///
+/// The code is url-safe base64 of the following bytes, where numbers of unbounded size
+/// are stored as LEB128 varints:
+/// - version, kind (with or without progress), width, height
+/// - the number of centers, followed by row and column in half-steps and size (0 for none) for each
+/// - the given walls, followed by the player's walls if the kind says so
+/// - a CRC-16 of everything before it
+///
+/// A set of walls is either a list of edge indices or a bit per edge, whichever is shorter.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SharedPuzzle {
+    pub width: usize,
+    pub height: usize,
+    pub objective: Objective,
+    pub player_walls: Option<HashSet<Border>>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ShareCodeError {
+    /// The code isn't url-safe base64
+    InvalidEncoding,
+    /// The code ends in the middle of the puzzle
+    Truncated,
+    /// The code has bytes after the checksum
+    TrailingData,
+    /// The checksum doesn't match, most likely because the code was mistyped or cut off
+    ChecksumMismatch,
+    /// The code was written by a version we don't know how to read
+    UnsupportedVersion(u8),
+    /// The code is neither a puzzle nor a puzzle with progress
+    UnsupportedKind(u8),
+    /// The board is empty or too large
+    InvalidDimensions(usize, usize),
+    /// A center lies (partly) outside the board
+    CenterOutOfBounds(Position),
+    /// A center appears more than once
+    DuplicateCenter(Position),
+    /// A wall refers to an edge that isn't on the board
+    WallOutOfBounds(usize),
+}
+
+impl Display for ShareCodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShareCodeError::InvalidEncoding => write!(f, "share code is not url-safe base64"),
+            ShareCodeError::Truncated => write!(f, "share code is truncated"),
+            ShareCodeError::TrailingData => write!(f, "share code has trailing data"),
+            ShareCodeError::ChecksumMismatch => write!(f, "share code checksum does not match"),
+            ShareCodeError::UnsupportedVersion(version) => {
+                write!(f, "unsupported share code version {version}")
+            }
+            ShareCodeError::UnsupportedKind(kind) => {
+                write!(f, "unsupported share code kind {kind}")
+            }
+            ShareCodeError::InvalidDimensions(width, height) => {
+                write!(f, "invalid board size {width}x{height}")
+            }
+            ShareCodeError::CenterOutOfBounds(center) => {
+                write!(f, "center {center} is outside the board")
+            }
+            ShareCodeError::DuplicateCenter(center) => write!(f, "center {center} is duplicated"),
+            ShareCodeError::WallOutOfBounds(index) => {
+                write!(f, "wall {index} is outside the board")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ShareCodeError {}
+
+impl From<CompactError> for ShareCodeError {
+    fn from(error: CompactError) -> Self {
+        match error {
+            CompactError::Truncated => ShareCodeError::Truncated,
+            CompactError::InvalidEncoding => ShareCodeError::InvalidEncoding,
+            CompactError::WallOutOfBounds(index) => ShareCodeError::WallOutOfBounds(index),
+        }
+    }
+}
+
+impl SharedPuzzle {
+    pub fn new(width: usize, height: usize, objective: Objective) -> Self {
+        SharedPuzzle {
+            width,
+            height,
+            objective,
+            player_walls: None,
+        }
+    }
+
+    /// Like [SharedPuzzle::new], including the walls the player has drawn that aren't given
+    pub fn with_progress(width: usize, height: usize, objective: Objective, board: &Board) -> Self {
+        let player_walls = board
+            .get_borders()
+            .filter(|border| !objective.walls.contains(border))
+            .collect();
+        SharedPuzzle {
+            width,
+            height,
+            objective,
+            player_walls: Some(player_walls),
+        }
+    }
+
+    /// Writes the share code, which fails if the board is too large for one
+    pub fn encode(&self) -> Result<String, ShareCodeError> {
+        if !(1..=MAX_SHARE_CODE_SIZE).contains(&self.width)
+            || !(1..=MAX_SHARE_CODE_SIZE).contains(&self.height)
+        {
+            return Err(ShareCodeError::InvalidDimensions(self.width, self.height));
+        }
+        let edges = Edges::new(self.width, self.height);
+        let mut bytes = vec![
+            SHARE_CODE_VERSION,
+            if self.player_walls.is_some() {
+                KIND_PUZZLE_WITH_PROGRESS
+            } else {
+                KIND_PUZZLE
+            },
+            self.width as u8,
+            self.height as u8,
+        ];
+
+        let mut centers: Vec<&GalaxyCenter> = self.objective.centers.iter().collect();
+        centers.sort();
+        write_varint(&mut bytes, centers.len());
+        for center in centers {
+            bytes.push(center.position.row as u8);
+            bytes.push(center.position.column as u8);
+            write_varint(&mut bytes, center.size.unwrap_or(0));
+        }
+
+        edges.write_walls(&mut bytes, &self.objective.walls);
+        if let Some(player_walls) = &self.player_walls {
+            edges.write_walls(&mut bytes, player_walls);
+        }
+
+        let checksum = crc16(&bytes);
+        bytes.extend_from_slice(&checksum.to_be_bytes());
+        Ok(URL_SAFE_NO_PAD.encode(bytes))
+    }
+
+    /// Reads a share code, checking that it's intact and that everything lies on the board
+    pub fn decode(code: &str) -> Result<SharedPuzzle, ShareCodeError> {
+        let bytes = URL_SAFE_NO_PAD
+            .decode(code.trim())
+            .map_err(|_| ShareCodeError::InvalidEncoding)?;
+        if bytes.len() < 2 {
+            return Err(ShareCodeError::Truncated);
+        }
+        let (payload, checksum) = bytes.split_at(bytes.len() - 2);
+        if crc16(payload).to_be_bytes() != checksum {
+            return Err(ShareCodeError::ChecksumMismatch);
+        }
+
+        let mut reader = Reader::new(payload);
+        let version = reader.read_byte()?;
+        if version != SHARE_CODE_VERSION {
+            return Err(ShareCodeError::UnsupportedVersion(version));
+        }
+        let kind = reader.read_byte()?;
+        if kind != KIND_PUZZLE && kind != KIND_PUZZLE_WITH_PROGRESS {
+            return Err(ShareCodeError::UnsupportedKind(kind));
+        }
+        let width = reader.read_byte()? as usize;
+        let height = reader.read_byte()? as usize;
+        if !(1..=MAX_SHARE_CODE_SIZE).contains(&width)
+            || !(1..=MAX_SHARE_CODE_SIZE).contains(&height)
+        {
+            return Err(ShareCodeError::InvalidDimensions(width, height));
+        }
+        let board = Board::new(width, height);
+        let edges = Edges::new(width, height);
+
+        let center_count = reader.read_varint()?;
+        let mut centers = HashSet::new();
+        let mut center_positions = HashSet::new();
+        for _ in 0..center_count {
+            let row = reader.read_byte()? as i32;
+            let column = reader.read_byte()? as i32;
+            let size = reader.read_varint()?;
+            let position = Position::new(row, column);
+            let on_board = position
+                .get_center_placement()
+                .get_positions()
+                .iter()
+                .all(|p| board.contains(p));
+            if !on_board {
+                return Err(ShareCodeError::CenterOutOfBounds(position));
+            }
+            if !center_positions.insert(position) {
+                return Err(ShareCodeError::DuplicateCenter(position));
+            }
+            centers.insert(GalaxyCenter {
+                position,
+                size: (size != 0).then_some(size),
+            });
+        }
+
+        let walls = edges.read_walls(&mut reader)?;
+        let player_walls = if kind == KIND_PUZZLE_WITH_PROGRESS {
+            Some(edges.read_walls(&mut reader)?)
+        } else {
+            None
+        };
+        if !reader.is_empty() {
+            return Err(ShareCodeError::TrailingData);
+        }
+
+        Ok(SharedPuzzle {
+            width,
+            height,
+            objective: Objective { centers, walls },
+            player_walls,
+        })
+    }
+}
+
+/// CRC-16/CCITT-FALSE
+fn crc16(bytes: &[u8]) -> u16 {
+    let mut crc: u16 = 0xffff;
+    for &byte in bytes {
+        crc ^= (byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::board::Board;
+    use crate::model::border::Border;
+    use crate::model::objective::{GalaxyCenter, Objective};
+    use crate::model::position::Position;
+    use crate::model::share_code::{crc16, ShareCodeError, SharedPuzzle, MAX_SHARE_CODE_SIZE};
+    use crate::model::universe::Universe;
+    use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    use base64::Engine;
+    use std::collections::HashSet;
+
+    fn border(r1: i32, c1: i32, r2: i32, c2: i32) -> Border {
+        Border::new(Position::new(r1, c1), Position::new(r2, c2))
+    }
+
+    fn some_puzzle() -> SharedPuzzle {
+        let centers = HashSet::from([
+            GalaxyCenter {
+                position: Position::new(0, 1),
+                size: Some(2),
+            },
+            GalaxyCenter {
+                position: Position::new(3, 3),
+                size: None,
+            },
+            GalaxyCenter {
+                position: Position::new(4, 0),
+                size: None,
+            },
+        ]);
+        let walls = HashSet::from([border(0, 0, 1, 0)]);
+        SharedPuzzle::new(3, 3, Objective { centers, walls })
+    }
+
+    /// Re-encodes the bytes of a code after tampering with them, keeping the checksum valid
+    fn tamper(code: &str, f: impl FnOnce(&mut Vec<u8>)) -> String {
+        let mut bytes = URL_SAFE_NO_PAD.decode(code).unwrap();
+        bytes.truncate(bytes.len() - 2);
+        f(&mut bytes);
+        let checksum = crc16(&bytes);
+        bytes.extend_from_slice(&checksum.to_be_bytes());
+        URL_SAFE_NO_PAD.encode(bytes)
+    }
+
+    #[test]
+    fn should_round_trip_puzzle() {
+        let puzzle = some_puzzle();
+        let code = puzzle.encode().unwrap();
+        assert!(code
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+        assert_eq!(SharedPuzzle::decode(&code), Ok(puzzle));
+    }
+
+    #[test]
+    fn should_round_trip_progress() {
+        let puzzle = some_puzzle();
+        let mut board = Board::new(3, 3);
+        board.add_wall(Position::new(0, 0), Position::new(1, 0));
+        board.add_wall(Position::new(1, 1), Position::new(1, 2));
+        board.add_wall(Position::new(2, 1), Position::new(2, 2));
+        let with_progress =
+            SharedPuzzle::with_progress(puzzle.width, puzzle.height, puzzle.objective, &board);
+        assert_eq!(
+            with_progress.player_walls,
+            Some(HashSet::from([border(1, 1, 1, 2), border(2, 1, 2, 2)]))
+        );
+        let decoded = SharedPuzzle::decode(&with_progress.encode().unwrap()).unwrap();
+        assert_eq!(decoded, with_progress);
+    }
+
+    #[test]
+    fn should_round_trip_dense_walls() {
+        let universe = Universe::generate(10, 7);
+        let mut board = Board::new(10, 7);
+        for galaxy in universe.get_galaxies() {
+            for border in galaxy.get_borders() {
+                if board.contains_border(&border) {
+                    board.add_wall(border.p1(), border.p2());
+                }
+            }
+        }
+        let puzzle = SharedPuzzle::with_progress(10, 7, Objective::generate(&universe), &board);
+        let code = puzzle.encode().unwrap();
+        assert_eq!(SharedPuzzle::decode(&code), Ok(puzzle));
+    }
+
+    #[test]
+    fn should_refuse_to_encode_large_boards() {
+        let puzzle = SharedPuzzle::new(MAX_SHARE_CODE_SIZE + 1, 2, some_puzzle().objective);
+        assert_eq!(
+            puzzle.encode(),
+            Err(ShareCodeError::InvalidDimensions(
+                MAX_SHARE_CODE_SIZE + 1,
+                2
+            ))
+        );
+    }
+
+    #[test]
+    fn should_reject_invalid_characters() {
+        assert_eq!(
+            SharedPuzzle::decode("not a code!"),
+            Err(ShareCodeError::InvalidEncoding)
+        );
+    }
+
+    #[test]
+    fn should_detect_typos() {
+        let code = some_puzzle().encode().unwrap();
+        let mut chars: Vec<char> = code.chars().collect();
+        chars[3] = if chars[3] == 'A' { 'B' } else { 'A' };
+        let typo: String = chars.into_iter().collect();
+        assert_eq!(
+            SharedPuzzle::decode(&typo),
+            Err(ShareCodeError::ChecksumMismatch)
+        );
+    }
+
+    #[test]
+    fn should_reject_truncated_code() {
+        let code = tamper(&some_puzzle().encode().unwrap(), |bytes| bytes.truncate(6));
+        assert_eq!(SharedPuzzle::decode(&code), Err(ShareCodeError::Truncated));
+    }
+
+    #[test]
+    fn should_reject_centers_outside_the_board() {
+        let code = tamper(&some_puzzle().encode().unwrap(), |bytes| {
+            // The first center is stored right after the header and the center count
+            bytes[5] = 5;
+        });
+        assert_eq!(
+            SharedPuzzle::decode(&code),
+            Err(ShareCodeError::CenterOutOfBounds(Position::new(5, 1)))
+        );
+    }
+
+    #[test]
+    fn should_reject_unknown_version() {
+        let code = tamper(&some_puzzle().encode().unwrap(), |bytes| bytes[0] = 9);
+        assert_eq!(
+            SharedPuzzle::decode(&code),
+            Err(ShareCodeError::UnsupportedVersion(9))
+        );
+    }
+}
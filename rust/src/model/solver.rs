@@ -0,0 +1,492 @@
+use crate::model::board::Board;
+use crate::model::border::Border;
+use crate::model::galaxy::Galaxy;
+use crate::model::objective::Objective;
+use crate::model::position::Position;
+use crate::model::universe::Universe;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet, VecDeque};
+use ts_rs::TS;
+
+/// The work the solver may do before it gives up, so that a large or pathological board
+/// can't freeze the game. A round of propagation costs the number of cells times the
+/// number of centers, which is about how many candidates it looks at.
+const WORK_LIMIT: usize = 2_000_000;
+
+/// How a search for a solution ended
+#[derive(Clone, Debug)]
+pub enum SolverOutcome {
+    Solved(Universe),
+    /// There is no solution, whatever the remaining walls
+    Unsolvable,
+    /// The search used up the work it was given, see [WORK_LIMIT]
+    Abandoned,
+}
+
+/// Whether the walls on a board can still be completed to a solution
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum Solvability {
+    Solvable,
+    /// The conflicting walls can't all be part of a solution, but any of them can go
+    /// without the others being enough for a conflict, unless the solver ran out of work
+    /// while narrowing them down. If there are none, the puzzle has no solution at all.
+    Unsolvable {
+        conflicting_walls: Vec<Border>,
+    },
+    /// The solver ran out of work before finding out
+    Unknown,
+}
+
+/// Finds the galaxies of a puzzle from the centers alone, without a stored solution.
+/// Walls are constraints: the cells on both sides of a wall belong to different galaxies.
+pub struct Solver {
+    width: usize,
+    height: usize,
+    /// The centers in half-steps
+    centers: Vec<Position>,
+    sizes: Vec<Option<usize>>,
+    walls: HashSet<Border>,
+}
+
+/// The centers each cell can still belong to, indexed by [Position::to_index]
+type Candidates = Vec<BTreeSet<usize>>;
+
+/// The work left for one or more searches, and the walls the last search relied on
+struct Effort {
+    remaining: usize,
+    /// The walls that removed a candidate or blocked a path. Without the other walls,
+    /// the search would have ended the same way.
+    used_walls: HashSet<Border>,
+}
+
+impl Effort {
+    fn new(work: usize) -> Self {
+        Effort {
+            remaining: work,
+            used_walls: HashSet::new(),
+        }
+    }
+
+    /// Takes the work from what's left. Returns false if there isn't enough.
+    fn spend(&mut self, work: usize) -> bool {
+        match self.remaining.checked_sub(work) {
+            Some(remaining) => {
+                self.remaining = remaining;
+                true
+            }
+            None => {
+                self.remaining = 0;
+                false
+            }
+        }
+    }
+}
+
+/// How propagating the constraints ended
+enum Propagation {
+    /// No more candidates can be removed, and every cell has one left
+    Settled,
+    Contradiction,
+    OutOfWork,
+}
+
+impl Solver {
+    /// Creates a solver for the objective, with its given walls as the only walls
+    pub fn new(width: usize, height: usize, objective: &Objective) -> Self {
+        let mut centers: Vec<_> = objective.centers.iter().collect();
+        centers.sort();
+        Solver {
+            width,
+            height,
+            centers: centers.iter().map(|center| center.position).collect(),
+            sizes: centers.iter().map(|center| center.size).collect(),
+            walls: objective.walls.clone(),
+        }
+    }
+
+    /// Adds walls that the solution must have
+    pub fn with_walls(mut self, walls: impl IntoIterator<Item = Border>) -> Self {
+        self.walls.extend(walls);
+        self
+    }
+
+    /// Searches for any solution, by propagating the constraints and guessing the galaxy
+    /// of the most constrained cell when they aren't enough
+    pub fn solve(&self) -> SolverOutcome {
+        self.solve_within(&mut Effort::new(WORK_LIMIT))
+    }
+
+    /// Like [Solver::solve], spending the work left in the effort
+    fn solve_within(&self, effort: &mut Effort) -> SolverOutcome {
+        effort.used_walls.clear();
+        let Some(candidates) = self.get_initial_candidates() else {
+            return SolverOutcome::Unsolvable;
+        };
+        self.search(candidates, effort)
+    }
+
+    /// Checks whether the walls on the board can be completed to a solution. If not, looks
+    /// for a minimal set of the board's walls that conflict. Only the walls the failing
+    /// search used can be in it, and those are left out one by one, keeping the ones
+    /// without which the board becomes solvable. All searches share one [WORK_LIMIT].
+    pub fn check_board(board: &Board, objective: &Objective) -> Solvability {
+        Solver::check_board_within(board, objective, WORK_LIMIT)
+    }
+
+    /// Like [Solver::check_board], with the given amount of work for all searches
+    fn check_board_within(board: &Board, objective: &Objective, work: usize) -> Solvability {
+        let mut walls: Vec<Border> = board
+            .get_borders()
+            .filter(|wall| !objective.walls.contains(wall))
+            .collect();
+        walls.sort();
+
+        let mut effort = Effort::new(work);
+        let mut solve_with = |walls: &[Border]| {
+            let outcome = Solver::new(board.get_width(), board.get_height(), objective)
+                .with_walls(walls.iter().copied())
+                .solve_within(&mut effort);
+            let used: Vec<Border> = walls
+                .iter()
+                .filter(|wall| effort.used_walls.contains(wall))
+                .copied()
+                .collect();
+            (outcome, used)
+        };
+        let mut conflicting_walls = match solve_with(&walls) {
+            (SolverOutcome::Solved(_), _) => return Solvability::Solvable,
+            (SolverOutcome::Abandoned, _) => return Solvability::Unknown,
+            (SolverOutcome::Unsolvable, used) => used,
+        };
+
+        let mut kept = 0;
+        while kept < conflicting_walls.len() {
+            let mut without = conflicting_walls.clone();
+            without.remove(kept);
+            match solve_with(&without) {
+                (SolverOutcome::Unsolvable, used) => conflicting_walls = used,
+                (SolverOutcome::Solved(_), _) => kept += 1,
+                // The walls found so far still conflict, some may just be unnecessary
+                (SolverOutcome::Abandoned, _) => break,
+            }
+        }
+        Solvability::Unsolvable { conflicting_walls }
+    }
+
+    fn contains(&self, cell: &Position) -> bool {
+        cell.row >= 0
+            && cell.row < self.height as i32
+            && cell.column >= 0
+            && cell.column < self.width as i32
+    }
+
+    fn get_cells(&self) -> impl Iterator<Item = Position> + use<'_> {
+        (0..self.height as i32)
+            .flat_map(move |row| (0..self.width as i32).map(move |column| (row, column).into()))
+    }
+
+    fn index(&self, cell: &Position) -> usize {
+        cell.to_index(self.width)
+    }
+
+    /// Each cell may belong to the centers its mirror image is on the board for, and the
+    /// cells around a center belong to it. Returns None if two centers share a cell.
+    fn get_initial_candidates(&self) -> Option<Candidates> {
+        let mut candidates: Candidates = self
+            .get_cells()
+            .map(|cell| {
+                (0..self.centers.len())
+                    .filter(|&id| self.contains(&self.centers[id].mirror_position(&cell)))
+                    .collect()
+            })
+            .collect();
+        for (id, center) in self.centers.iter().enumerate() {
+            for cell in center.get_center_placement().get_positions() {
+                if !self.contains(&cell) {
+                    return None;
+                }
+                let index = self.index(&cell);
+                if !candidates[index].contains(&id) {
+                    return None;
+                }
+                candidates[index] = BTreeSet::from([id]);
+            }
+        }
+        Some(candidates)
+    }
+
+    fn search(&self, mut candidates: Candidates, effort: &mut Effort) -> SolverOutcome {
+        match self.propagate(&mut candidates, effort) {
+            Propagation::Settled => {}
+            Propagation::Contradiction => return SolverOutcome::Unsolvable,
+            Propagation::OutOfWork => return SolverOutcome::Abandoned,
+        }
+        let guess = candidates
+            .iter()
+            .enumerate()
+            .filter(|(_, ids)| ids.len() > 1)
+            .min_by_key(|(_, ids)| ids.len())
+            .map(|(index, ids)| (index, ids.clone()));
+        let Some((index, ids)) = guess else {
+            return self.to_solution(&candidates);
+        };
+
+        for id in ids {
+            let mut guessed = candidates.clone();
+            guessed[index] = BTreeSet::from([id]);
+            match self.search(guessed, effort) {
+                SolverOutcome::Unsolvable => {}
+                outcome => return outcome,
+            }
+        }
+        SolverOutcome::Unsolvable
+    }
+
+    /// Removes candidates until none can be removed. A cell can't belong to a center if its
+    /// mirror image can't, if it's across a wall from a cell that belongs to the center, or
+    /// if it can't be connected to the center. The walls that play a part are noted as used.
+    fn propagate(&self, candidates: &mut Candidates, effort: &mut Effort) -> Propagation {
+        let mut changed = true;
+        while changed {
+            if !effort.spend(candidates.len() * self.centers.len().max(1)) {
+                return Propagation::OutOfWork;
+            }
+            changed = false;
+            for cell in self.get_cells() {
+                let index = self.index(&cell);
+                let ids = candidates[index].clone();
+                for id in ids {
+                    let mirror = self.centers[id].mirror_position(&cell);
+                    if !candidates[self.index(&mirror)].contains(&id) {
+                        candidates[index].remove(&id);
+                        changed = true;
+                    }
+                }
+            }
+
+            for wall in &self.walls {
+                for (from, to) in [(wall.p1(), wall.p2()), (wall.p2(), wall.p1())] {
+                    let ids = &candidates[self.index(&from)];
+                    if ids.len() == 1 {
+                        let id = *ids.first().unwrap();
+                        if candidates[self.index(&to)].remove(&id) {
+                            effort.used_walls.insert(*wall);
+                            changed = true;
+                        }
+                    }
+                }
+            }
+
+            for id in 0..self.centers.len() {
+                let reachable = self.get_reachable(candidates, id, &mut effort.used_walls);
+                for cell in self.get_cells() {
+                    if !reachable.contains(&cell) {
+                        changed |= candidates[self.index(&cell)].remove(&id);
+                    }
+                }
+                if self.sizes[id].is_some_and(|size| reachable.len() < size) {
+                    return Propagation::Contradiction;
+                }
+            }
+
+            if candidates.iter().any(BTreeSet::is_empty) {
+                return Propagation::Contradiction;
+            }
+        }
+        Propagation::Settled
+    }
+
+    /// Returns the cells that can be connected to the center through cells that may
+    /// belong to it, without crossing a wall. The walls in the way are added to the used walls.
+    fn get_reachable(
+        &self,
+        candidates: &Candidates,
+        id: usize,
+        used_walls: &mut HashSet<Border>,
+    ) -> HashSet<Position> {
+        let mut queue: VecDeque<Position> = self.centers[id]
+            .get_center_placement()
+            .get_positions()
+            .into_iter()
+            .collect();
+        let mut reachable: HashSet<Position> = queue.iter().copied().collect();
+        while let Some(cell) = queue.pop_front() {
+            for neighbour in cell.adjacent() {
+                if !self.contains(&neighbour)
+                    || reachable.contains(&neighbour)
+                    || !candidates[self.index(&neighbour)].contains(&id)
+                {
+                    continue;
+                }
+                let border = Border::new(cell, neighbour);
+                if self.walls.contains(&border) {
+                    used_walls.insert(border);
+                } else {
+                    reachable.insert(neighbour);
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+        reachable
+    }
+
+    /// Turns candidates with a single center per cell into galaxies, checking their sizes
+    fn to_solution(&self, candidates: &Candidates) -> SolverOutcome {
+        let mut galaxies = vec![Galaxy::new(); self.centers.len()];
+        for cell in self.get_cells() {
+            let id = *candidates[self.index(&cell)].first().unwrap();
+            galaxies[id].add_position(cell);
+        }
+        let sizes_match = galaxies
+            .iter()
+            .zip(&self.sizes)
+            .all(|(galaxy, size)| size.is_none_or(|size| galaxy.size() == size));
+        if sizes_match {
+            SolverOutcome::Solved(Universe::from(galaxies.as_slice()))
+        } else {
+            SolverOutcome::Unsolvable
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::board::Board;
+    use crate::model::border::Border;
+    use crate::model::galaxy::Galaxy;
+    use crate::model::objective::Objective;
+    use crate::model::position::Position;
+    use crate::model::solver::{Solvability, Solver, SolverOutcome};
+    use crate::model::universe::Universe;
+    use std::collections::HashSet;
+
+    fn border(r1: i32, c1: i32, r2: i32, c2: i32) -> Border {
+        Border::new(Position::new(r1, c1), Position::new(r2, c2))
+    }
+
+    /// A 4 by 2 board with two 2 by 2 galaxies
+    fn two_squares() -> Objective {
+        let galaxies = [
+            Galaxy::from([(0, 0), (0, 1), (1, 0), (1, 1)]),
+            Galaxy::from([(0, 2), (0, 3), (1, 2), (1, 3)]),
+        ];
+        Objective::generate(&Universe::from(galaxies.as_slice()))
+    }
+
+    /// A 10 by 10 board tiled with rectangles of many sizes
+    fn rectangles() -> Universe {
+        let rectangle = |rows: std::ops::Range<i32>, columns: std::ops::Range<i32>| {
+            Galaxy::from(rows.flat_map(move |row| columns.clone().map(move |column| (row, column))))
+        };
+        let galaxies = [
+            rectangle(0..2, 0..2),
+            rectangle(0..2, 2..5),
+            rectangle(0..2, 5..6),
+            rectangle(0..2, 6..10),
+            rectangle(2..5, 0..3),
+            rectangle(2..5, 3..4),
+            rectangle(2..5, 4..9),
+            rectangle(2..5, 9..10),
+            rectangle(5..7, 0..5),
+            rectangle(5..7, 5..7),
+            rectangle(5..7, 7..10),
+            rectangle(7..10, 0..1),
+            rectangle(7..10, 1..4),
+            rectangle(7..10, 4..8),
+            rectangle(7..10, 8..10),
+        ];
+        Universe::from(galaxies.as_slice())
+    }
+
+    #[test]
+    fn checks_should_give_up_when_out_of_work() {
+        let universe = rectangles();
+        let objective = Objective::generate(&universe);
+        let mut board = Board::new(10, 10);
+        let solution_walls: HashSet<Border> = universe.get_borders().collect();
+        for wall in solution_walls.iter().filter(|wall| wall.p2().row < 5) {
+            board.add_wall(wall.p1(), wall.p2());
+        }
+        for row in 5..9 {
+            for column in 0..9 {
+                if (row + column) % 3 == 0 {
+                    board.add_wall(Position::new(row, column), Position::new(row, column + 1));
+                }
+            }
+        }
+
+        // A single round of propagation looks at every center for each of the 100 cells
+        let round = 100 * objective.centers.len();
+        assert_eq!(
+            Solver::check_board_within(&board, &objective, round - 1),
+            Solvability::Unknown
+        );
+        let Solvability::Unsolvable { conflicting_walls } = Solver::check_board(&board, &objective)
+        else {
+            panic!("the board has wrong walls");
+        };
+        assert!(conflicting_walls
+            .iter()
+            .any(|wall| !solution_walls.contains(wall)));
+    }
+
+    #[test]
+    fn should_solve_generated_puzzles() {
+        let universe = Universe::generate(6, 6);
+        let objective = Objective::generate(&universe);
+        let SolverOutcome::Solved(solution) = Solver::new(6, 6, &objective).solve() else {
+            panic!("generated puzzles have a solution");
+        };
+        let galaxies = solution.get_galaxies();
+        assert_eq!(galaxies.len(), objective.centers.len());
+        for galaxy in galaxies {
+            assert!(galaxy.is_valid());
+            assert!(objective
+                .centers
+                .iter()
+                .any(|center| center.position == galaxy.center()));
+        }
+    }
+
+    #[test]
+    fn correct_walls_should_keep_the_board_solvable() {
+        let objective = two_squares();
+        let mut board = Board::new(4, 2);
+        board.add_wall(Position::new(0, 1), Position::new(0, 2));
+        assert_eq!(
+            Solver::check_board(&board, &objective),
+            Solvability::Solvable
+        );
+    }
+
+    #[test]
+    fn conflicts_should_be_minimal() {
+        let objective = two_squares();
+        let mut board = Board::new(4, 2);
+        board.add_wall(Position::new(0, 1), Position::new(0, 2));
+        board.add_wall(Position::new(1, 1), Position::new(1, 2));
+        board.add_wall(Position::new(0, 3), Position::new(1, 3));
+        board.add_wall(Position::new(0, 0), Position::new(1, 0));
+
+        assert_eq!(
+            Solver::check_board(&board, &objective),
+            Solvability::Unsolvable {
+                conflicting_walls: vec![border(0, 3, 1, 3)]
+            }
+        );
+    }
+
+    #[test]
+    fn given_walls_should_not_be_reported() {
+        let mut objective = two_squares();
+        objective.walls.insert(border(0, 0, 0, 1));
+        let board = Board::new(4, 2);
+        assert_eq!(
+            Solver::check_board(&board, &objective),
+            Solvability::Unsolvable {
+                conflicting_walls: vec![]
+            }
+        );
+    }
+}
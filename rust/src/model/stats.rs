@@ -0,0 +1,129 @@
+use crate::model::puzzle::Difficulty;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// The time spent actively solving. The host supplies the timestamps, in milliseconds,
+/// and pauses the timer while the game isn't shown.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Timer {
+    /// The time counted up to the last timestamp
+    elapsed_ms: u64,
+    /// The last timestamp while the timer is running
+    running_since: Option<u64>,
+}
+
+impl Timer {
+    pub fn is_running(&self) -> bool {
+        self.running_since.is_some()
+    }
+
+    /// Returns the time counted up to the last timestamp the host supplied
+    pub fn get_elapsed_ms(&self) -> u64 {
+        self.elapsed_ms
+    }
+
+    /// Starts counting from now, if the timer isn't running yet
+    pub fn resume(&mut self, now: u64) {
+        if self.running_since.is_none() {
+            self.running_since = Some(now);
+        }
+    }
+
+    /// Counts the time up to now, and stops counting
+    pub fn pause(&mut self, now: u64) {
+        if let Some(since) = self.running_since.take() {
+            // A clock that jumps backwards adds nothing
+            self.elapsed_ms += now.saturating_sub(since);
+        }
+    }
+
+    /// Counts the time up to now, and keeps counting
+    pub fn tick(&mut self, now: u64) {
+        if self.is_running() {
+            self.pause(now);
+            self.resume(now);
+        }
+    }
+
+    /// Stops counting at the last timestamp
+    pub fn stop(&mut self) {
+        self.running_since = None;
+    }
+}
+
+/// What the player has done so far
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct Stats {
+    /// Moves made, not counting undo, redo and hints
+    pub moves: usize,
+    pub undos: usize,
+    /// The number of times the player checked the solution
+    pub checks: usize,
+    /// The number of errors found by each check, in order
+    pub errors_per_check: Vec<usize>,
+    /// The number of times the player compared their walls with the solution
+    pub progress_checks: usize,
+    /// Hints taken of each tier, including undone ones
+    pub mistakes_located: usize,
+    pub walls_revealed: usize,
+    pub galaxies_revealed: usize,
+    /// The sum of the costs of the hints taken
+    pub hint_cost: usize,
+    /// Whether the player revealed the solution before solving the puzzle
+    #[serde(default)]
+    pub gave_up: bool,
+}
+
+/// How a puzzle was solved, produced once when it's solved
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct CompletionSummary {
+    pub width: usize,
+    pub height: usize,
+    pub difficulty: Difficulty,
+    /// The active solving time, in milliseconds
+    #[ts(type = "number")]
+    pub elapsed_ms: u64,
+    pub hints_used: usize,
+    pub stats: Stats,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::stats::Timer;
+
+    #[test]
+    fn timer_should_only_count_while_running() {
+        let mut timer = Timer::default();
+        timer.pause(500);
+        timer.resume(1000);
+        timer.tick(1500);
+        assert_eq!(timer.get_elapsed_ms(), 500);
+
+        timer.pause(2000);
+        timer.tick(9000);
+        timer.resume(10_000);
+        timer.resume(10_500);
+        timer.pause(11_000);
+        assert_eq!(timer.get_elapsed_ms(), 2000);
+    }
+
+    #[test]
+    fn timer_should_ignore_clocks_going_backwards() {
+        let mut timer = Timer::default();
+        timer.resume(1000);
+        timer.pause(400);
+        assert_eq!(timer.get_elapsed_ms(), 0);
+    }
+
+    #[test]
+    fn stopped_timer_should_keep_the_time_of_the_last_tick() {
+        let mut timer = Timer::default();
+        timer.resume(0);
+        timer.tick(700);
+        timer.stop();
+        assert!(!timer.is_running());
+        assert_eq!(timer.get_elapsed_ms(), 700);
+    }
+}
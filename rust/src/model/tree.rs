@@ -3,7 +3,7 @@ use crate::model::rectangle::Rectangle;
 use std::collections::hash_map::Iter;
 use std::collections::HashMap;
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct Tree {
     parents: HashMap<Position, Option<Position>>,
 }
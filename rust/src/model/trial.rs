@@ -0,0 +1,31 @@
+use crate::model::board::Board;
+use crate::model::border::Border;
+use crate::model::history::NodeId;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// A hypothesis the player is testing, which is either kept or rolled back as a whole
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Trial {
+    /// The node of the history the trial started at, which abandoning it jumps back to
+    pub checkpoint: NodeId,
+    /// The walls on the board when the trial started
+    pub walls: BTreeSet<Border>,
+}
+
+impl Trial {
+    pub fn begin(checkpoint: NodeId, board: &Board) -> Self {
+        Trial {
+            checkpoint,
+            walls: board.get_borders().collect(),
+        }
+    }
+
+    /// Returns the walls on the board that weren't there when the trial started
+    pub fn get_new_walls(&self, board: &Board) -> Vec<Border> {
+        board
+            .get_borders()
+            .filter(|wall| !self.walls.contains(wall))
+            .collect()
+    }
+}
@@ -1,3 +1,4 @@
+use crate::model::border::Border;
 use crate::model::galaxy::Galaxy;
 use crate::model::position::Position;
 use crate::model::vec2::Vec2;
@@ -6,11 +7,12 @@ use ordered_float::OrderedFloat;
 use rand::prelude::SliceRandom;
 use rand::rngs::StdRng;
 use rand::{random, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::fmt::{Display, Formatter};
 use std::ops::{Index, IndexMut};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Universe {
     grid: Vec<Vec<usize>>,
 }
@@ -594,26 +596,35 @@ impl Universe {
         })
     }
 
-    fn get_width(&self) -> usize {
+    pub fn get_width(&self) -> usize {
         self.grid.first().map(|row| row.len()).unwrap_or(0)
     }
 
-    fn get_height(&self) -> usize {
+    pub fn get_height(&self) -> usize {
         self.grid.len()
     }
 
+    /// Returns true iff all rows have the same width and no id is larger than the
+    /// number of cells, which is what the rest of the universe assumes about a grid
+    /// that didn't come from [Universe::generate]
+    pub fn is_well_formed(&self) -> bool {
+        let width = self.get_width();
+        let size = width * self.get_height();
+        self.grid.iter().all(|row| row.len() == width) && self.get_ids().all(|&id| id <= size)
+    }
+
+    /// Returns the smallest id not used by any cell. There are more candidate ids
+    /// than cells, so the returned id is never larger than the number of cells.
     fn get_next_available_id(&self) -> usize {
         let size = self.get_width() * self.get_height();
-        let mut id_in_use = vec![false; size];
+        let mut id_in_use = vec![false; size + 1];
         for &id in self.get_ids() {
             id_in_use[id] = true;
         }
-        for (id, in_use) in id_in_use.into_iter().enumerate() {
-            if !in_use {
-                return id;
-            }
-        }
-        size
+        id_in_use
+            .into_iter()
+            .position(|in_use| !in_use)
+            .unwrap_or(size)
     }
 
     /// Returns a list of galaxies in this universe, in no particular order,
@@ -627,6 +638,17 @@ impl Universe {
             .collect()
     }
 
+    /// Returns the borders between adjacent positions in different galaxies,
+    /// i.e. the walls of the solved board
+    pub fn get_borders(&self) -> impl Iterator<Item = Border> + '_ {
+        self.get_positions().flat_map(move |p| {
+            [p.right(), p.down()]
+                .into_iter()
+                .filter(move |q| self.is_inside(q) && !self.are_neighbours(&p, q))
+                .map(move |q| Border::new(p, q))
+        })
+    }
+
     /// Make p have no neighbours
     pub fn remove_all_neighbours(&mut self, p: &Position) {
         self[p] = self.get_next_available_id();
@@ -0,0 +1,271 @@
+use crate::model::puzzle::{Difficulty, Puzzle};
+use crate::render::layout::Layout;
+use crate::render::svg::{number, render_puzzle, render_solution, Frame, SvgOptions};
+use std::fmt::Write;
+
+const LABEL_HEIGHT: f64 = 7.0;
+const HEADER_HEIGHT: f64 = 12.0;
+const FOOTER_HEIGHT: f64 = 8.0;
+const SLOT_PADDING: f64 = 4.0;
+
+/// How to lay out a booklet. Lengths are in millimeters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BookletOptions {
+    pub page_width: f64,
+    pub page_height: f64,
+    pub margin: f64,
+    pub puzzles_per_page: usize,
+    /// Printed at the top of every puzzle page
+    pub title: Option<String>,
+}
+
+impl Default for BookletOptions {
+    /// Four puzzles per A4 page
+    fn default() -> Self {
+        BookletOptions {
+            page_width: 210.0,
+            page_height: 297.0,
+            margin: 15.0,
+            puzzles_per_page: 4,
+            title: None,
+        }
+    }
+}
+
+impl BookletOptions {
+    /// Returns the number of columns and rows of puzzles on a page
+    fn get_grid(&self) -> (usize, usize) {
+        let count = self.puzzles_per_page.max(1);
+        let columns = (count as f64).sqrt().ceil() as usize;
+        let rows = count.div_ceil(columns);
+        (columns, rows)
+    }
+
+    /// Returns the area of the page reserved for the puzzle at the given index on the page
+    fn get_slot(&self, index: usize) -> Frame {
+        let (columns, rows) = self.get_grid();
+        let width = (self.page_width - 2.0 * self.margin) / columns as f64;
+        let height =
+            (self.page_height - 2.0 * self.margin - HEADER_HEIGHT - FOOTER_HEIGHT) / rows as f64;
+        Frame {
+            x: self.margin + (index % columns) as f64 * width,
+            y: self.margin + HEADER_HEIGHT + (index / columns) as f64 * height,
+            width,
+            height,
+        }
+    }
+}
+
+/// Returns the label printed above a puzzle, e.g. `#003 · Medium · 10×10`.
+/// Puzzles without an id are numbered after their position in the booklet.
+pub fn puzzle_label(index: usize, puzzle: &Puzzle) -> String {
+    let id = puzzle
+        .metadata
+        .id
+        .clone()
+        .unwrap_or_else(|| format!("{:03}", index + 1));
+    let difficulty = puzzle
+        .metadata
+        .difficulty
+        .unwrap_or_else(|| Difficulty::estimate(&puzzle.solution, &puzzle.objective));
+    format!("#{id} · {difficulty} · {}×{}", puzzle.width, puzzle.height)
+}
+
+/// Renders the puzzles as printable svg pages, followed by pages with their answers
+pub fn render_booklet(puzzles: &[Puzzle], options: &BookletOptions) -> Vec<String> {
+    let per_page = options.puzzles_per_page.max(1);
+    let labelled: Vec<(String, &Puzzle)> = puzzles
+        .iter()
+        .enumerate()
+        .map(|(index, puzzle)| (puzzle_label(index, puzzle), puzzle))
+        .collect();
+    let chunks: Vec<&[(String, &Puzzle)]> = labelled.chunks(per_page).collect();
+    let page_count = 2 * chunks.len();
+
+    let puzzle_pages = chunks.iter().enumerate().map(|(page, chunk)| {
+        render_page(
+            options,
+            options.title.as_deref(),
+            chunk,
+            page,
+            page_count,
+            |puzzle, svg_options| {
+                render_puzzle(puzzle.width, puzzle.height, &puzzle.objective, svg_options)
+            },
+        )
+    });
+    let answer_pages = chunks.iter().enumerate().map(|(page, chunk)| {
+        render_page(
+            options,
+            Some("Answers"),
+            chunk,
+            chunks.len() + page,
+            page_count,
+            |puzzle, svg_options| render_solution(&puzzle.objective, &puzzle.solution, svg_options),
+        )
+    });
+    puzzle_pages.chain(answer_pages).collect()
+}
+
+fn render_page(
+    options: &BookletOptions,
+    heading: Option<&str>,
+    puzzles: &[(String, &Puzzle)],
+    page: usize,
+    page_count: usize,
+    render: impl Fn(&Puzzle, &SvgOptions) -> String,
+) -> String {
+    let width = number(options.page_width);
+    let height = number(options.page_height);
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {width} {height}" width="{width}mm" height="{height}mm">"#
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        r#"<rect width="{width}" height="{height}" fill="white"/>"#
+    )
+    .unwrap();
+    if let Some(heading) = heading {
+        write_text(
+            &mut svg,
+            options.page_width / 2.0,
+            options.margin + HEADER_HEIGHT / 2.0,
+            6.0,
+            heading,
+        );
+    }
+
+    for (index, (label, puzzle)) in puzzles.iter().enumerate() {
+        let slot = options.get_slot(index);
+        write_text(
+            &mut svg,
+            slot.x + slot.width / 2.0,
+            slot.y + LABEL_HEIGHT / 2.0,
+            4.0,
+            label,
+        );
+        let svg_options = SvgOptions {
+            frame: Some(fit(puzzle, &slot)),
+            ..SvgOptions::default()
+        };
+        svg.push_str(&render(puzzle, &svg_options));
+    }
+
+    write_text(
+        &mut svg,
+        options.page_width / 2.0,
+        options.page_height - options.margin - FOOTER_HEIGHT / 2.0,
+        3.5,
+        &format!("{} / {page_count}", page + 1),
+    );
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Returns the largest frame with the aspect ratio of the puzzle's image that
+/// fits in the slot below the label, centered horizontally
+fn fit(puzzle: &Puzzle, slot: &Frame) -> Frame {
+    let layout = Layout::new(puzzle.width, puzzle.height, 1.0);
+    let available_width = slot.width - 2.0 * SLOT_PADDING;
+    let available_height = slot.height - LABEL_HEIGHT - 2.0 * SLOT_PADDING;
+    let scale = (available_width / layout.image_width())
+        .min(available_height / layout.image_height())
+        .max(0.0);
+    let width = layout.image_width() * scale;
+    let height = layout.image_height() * scale;
+    Frame {
+        x: slot.x + (slot.width - width) / 2.0,
+        y: slot.y + LABEL_HEIGHT + SLOT_PADDING,
+        width,
+        height,
+    }
+}
+
+fn write_text(svg: &mut String, x: f64, y: f64, size: f64, text: &str) {
+    writeln!(
+        svg,
+        r#"<text x="{}" y="{}" font-family="sans-serif" font-size="{}" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+        number(x),
+        number(y),
+        number(size),
+        escape(text),
+    )
+    .unwrap();
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::galaxy::Galaxy;
+    use crate::model::objective::Objective;
+    use crate::model::puzzle::Puzzle;
+    use crate::model::universe::Universe;
+    use crate::render::booklet::{puzzle_label, render_booklet, BookletOptions};
+
+    fn small_puzzle() -> Puzzle {
+        let galaxies = [
+            Galaxy::from([(0, 0), (0, 1)]),
+            Galaxy::from([(1, 0)]),
+            Galaxy::from([(1, 1)]),
+        ];
+        let solution = Universe::from(galaxies.as_slice());
+        let objective = Objective::generate(&solution);
+        Puzzle::new(solution, objective)
+    }
+
+    #[test]
+    fn label_should_have_id_difficulty_and_size() {
+        let mut puzzle = small_puzzle();
+        assert_eq!(puzzle_label(2, &puzzle), "#003 · Easy · 2×2");
+        puzzle.metadata.id = Some("galaxy-7".to_string());
+        assert_eq!(puzzle_label(2, &puzzle), "#galaxy-7 · Easy · 2×2");
+    }
+
+    #[test]
+    fn answers_should_follow_puzzles() {
+        let puzzles = vec![small_puzzle(); 5];
+        let options = BookletOptions {
+            title: Some("Laniakea & friends".to_string()),
+            ..BookletOptions::default()
+        };
+
+        let pages = render_booklet(&puzzles, &options);
+
+        assert_eq!(pages.len(), 4);
+        for page in &pages {
+            assert!(page.starts_with("<svg xmlns"));
+            assert!(page.ends_with("</svg>\n"));
+        }
+        assert!(pages[0].contains("Laniakea &amp; friends"));
+        assert_eq!(pages[0].matches("<svg x=").count(), 4);
+        assert_eq!(pages[1].matches("<svg x=").count(), 1);
+        assert!(pages[1].contains("#005 · Easy · 2×2"));
+        assert!(pages[1].contains(">2 / 4</text>"));
+        assert!(pages[2].contains(">Answers</text>"));
+        assert!(pages[2].contains("#001 · Easy · 2×2"));
+        assert_eq!(pages[3].matches("<svg x=").count(), 1);
+    }
+
+    #[test]
+    fn puzzles_should_fit_in_their_slot() {
+        let options = BookletOptions {
+            puzzles_per_page: 6,
+            ..BookletOptions::default()
+        };
+        assert_eq!(options.get_grid(), (3, 2));
+        for index in 0..6 {
+            let slot = options.get_slot(index);
+            assert!(slot.x >= options.margin);
+            assert!(slot.x + slot.width <= options.page_width - options.margin + 1e-9);
+            assert!(slot.y + slot.height <= options.page_height - options.margin + 1e-9);
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use crate::model::board::Board;
+use crate::model::border::Border;
+use crate::model::objective::{GalaxyCenter, Objective};
+use crate::model::position::Position;
+
+/// The thickness of a wall relative to the size of a cell, same as in the board UI
+pub const WALL_CELL_RATIO: f64 = 0.1;
+
+/// Where things go when drawing a board, following the same rules as the board UI:
+/// grid lines are a wall thick and sit between the cells, and the outer frame is
+/// centered on the outermost grid lines.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Layout {
+    pub width: usize,
+    pub height: usize,
+    pub cell_size: f64,
+    pub wall_size: f64,
+}
+
+impl Layout {
+    pub fn new(width: usize, height: usize, cell_size: f64) -> Self {
+        Layout {
+            width,
+            height,
+            cell_size,
+            wall_size: cell_size * WALL_CELL_RATIO,
+        }
+    }
+
+    /// The distance between two adjacent grid lines
+    pub fn pitch(&self) -> f64 {
+        self.cell_size + self.wall_size
+    }
+
+    pub fn image_width(&self) -> f64 {
+        self.width as f64 * self.cell_size + (self.width + 1) as f64 * self.wall_size
+    }
+
+    pub fn image_height(&self) -> f64 {
+        self.height as f64 * self.cell_size + (self.height + 1) as f64 * self.wall_size
+    }
+
+    /// The x coordinate of the middle of the grid line to the left of the given column
+    pub fn line_x(&self, column: usize) -> f64 {
+        self.wall_size / 2.0 + self.pitch() * column as f64
+    }
+
+    /// The y coordinate of the middle of the grid line above the given row
+    pub fn line_y(&self, row: usize) -> f64 {
+        self.wall_size / 2.0 + self.pitch() * row as f64
+    }
+
+    /// The middle of the given cell
+    pub fn cell_center(&self, cell: &Position) -> (f64, f64) {
+        (
+            self.line_x(cell.column as usize) + self.pitch() / 2.0,
+            self.line_y(cell.row as usize) + self.pitch() / 2.0,
+        )
+    }
+
+    /// The point where a center, in half-steps, is drawn: in the middle of the cells around it
+    pub fn center_point(&self, center: &Position) -> (f64, f64) {
+        let cells = center.get_center_placement().get_positions();
+        let (x, y) = cells
+            .iter()
+            .map(|cell| self.cell_center(cell))
+            .fold((0.0, 0.0), |(x, y), (cx, cy)| (x + cx, y + cy));
+        (x / cells.len() as f64, y / cells.len() as f64)
+    }
+
+    pub fn center_radius(&self) -> f64 {
+        self.cell_size / 2.5 - self.wall_size
+    }
+
+    /// Returns the end points of the middle of the line drawn for the border
+    pub fn border_line(&self, border: &Border) -> ((f64, f64), (f64, f64)) {
+        let p2 = border.p2();
+        let (row, column) = (p2.row as usize, p2.column as usize);
+        if border.is_vertical() {
+            let x = self.line_x(column);
+            ((x, self.line_y(row)), (x, self.line_y(row + 1)))
+        } else {
+            let y = self.line_y(row);
+            ((self.line_x(column), y), (self.line_x(column + 1), y))
+        }
+    }
+}
+
+/// Splits the interior borders of the board into grid lines and walls
+pub fn partition_borders(board: &Board) -> (Vec<Border>, Vec<Border>) {
+    let mut grid = Vec::new();
+    let mut walls = Vec::new();
+    for (row, columns) in board.get_vertical_borders().iter().enumerate() {
+        for (column, &active) in columns.iter().enumerate() {
+            let p1 = Position::from((row, column));
+            let border = Border::new(p1, p1.right());
+            if active { &mut walls } else { &mut grid }.push(border);
+        }
+    }
+    for (row, columns) in board.get_horizontal_borders().iter().enumerate() {
+        for (column, &active) in columns.iter().enumerate() {
+            let p1 = Position::from((row, column));
+            let border = Border::new(p1, p1.down());
+            if active { &mut walls } else { &mut grid }.push(border);
+        }
+    }
+    (grid, walls)
+}
+
+/// Returns the centers of the objective in a stable order, used for assigning colors
+pub fn sorted_centers(objective: &Objective) -> Vec<GalaxyCenter> {
+    let mut centers: Vec<GalaxyCenter> = objective.centers.iter().copied().collect();
+    centers.sort();
+    centers
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::position::Position;
+    use crate::render::layout::Layout;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn centers_should_be_drawn_between_their_cells() {
+        let layout = Layout::new(3, 3, 10.0);
+        let (x0, y0) = layout.cell_center(&Position::new(0, 0));
+        let (x1, y1) = layout.cell_center(&Position::new(1, 1));
+        assert_relative_eq!(x0, 6.0);
+        assert_relative_eq!(y0, 6.0);
+
+        let (x, y) = layout.center_point(&Position::new(0, 0));
+        assert_relative_eq!(x, x0);
+        assert_relative_eq!(y, y0);
+
+        let (x, y) = layout.center_point(&Position::new(1, 1));
+        assert_relative_eq!(x, (x0 + x1) / 2.0);
+        assert_relative_eq!(y, (y0 + y1) / 2.0);
+
+        let (x, y) = layout.center_point(&Position::new(0, 1));
+        assert_relative_eq!(x, layout.line_x(1));
+        assert_relative_eq!(y, y0);
+    }
+
+    #[test]
+    fn image_should_fit_cells_and_walls() {
+        let layout = Layout::new(4, 2, 10.0);
+        assert_relative_eq!(layout.image_width(), 45.0);
+        assert_relative_eq!(layout.image_height(), 23.0);
+        assert_relative_eq!(layout.line_x(4) + layout.wall_size / 2.0, 45.0);
+    }
+}
@@ -0,0 +1,5 @@
+pub mod booklet;
+pub mod layout;
+pub mod png;
+pub mod svg;
+pub mod tikz;
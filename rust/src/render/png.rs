@@ -0,0 +1,436 @@
+use crate::model::board::Board;
+use crate::model::objective::Objective;
+use crate::model::position::Position;
+use crate::model::universe::Universe;
+use crate::render::layout::{partition_borders, sorted_centers, Layout};
+use crate::render::svg::DEFAULT_PALETTE;
+use std::collections::HashMap;
+
+pub type Rgb = [u8; 3];
+
+/// Digits drawn in a 3 by 5 grid, one row per element, most significant bit to the left
+const DIGITS: [[u8; 5]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b010, 0b010, 0b010],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+];
+
+/// The opacity of galaxy fills in the solution, same as in the svg
+const FILL_OPACITY: f64 = 0.35;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PngOptions {
+    /// The size of a cell in pixels. Sizes below one pixel are drawn at one pixel.
+    pub cell_size: f64,
+    pub background: Rgb,
+    pub grid: Rgb,
+    pub wall: Rgb,
+    pub center: Rgb,
+    pub center_text: Rgb,
+    /// Colors assigned to the centers in order. With a palette, centers are drawn
+    /// in their color and galaxies in the solution are filled with it.
+    pub palette: Option<Vec<Rgb>>,
+}
+
+impl Default for PngOptions {
+    fn default() -> Self {
+        PngOptions {
+            cell_size: 24.0,
+            background: [255, 255, 255],
+            grid: [200, 200, 200],
+            wall: [0, 0, 0],
+            center: [0, 0, 0],
+            center_text: [255, 255, 255],
+            palette: None,
+        }
+    }
+}
+
+impl PngOptions {
+    pub fn with_default_palette(self) -> Self {
+        PngOptions {
+            palette: Some(
+                DEFAULT_PALETTE
+                    .iter()
+                    .filter_map(|c| parse_hex(c))
+                    .collect(),
+            ),
+            ..self
+        }
+    }
+
+    /// Returns the layout of the board, with the cell size clamped so the image isn't empty
+    fn get_layout(&self, width: usize, height: usize) -> Layout {
+        Layout::new(width, height, self.cell_size.max(1.0))
+    }
+
+    /// Returns the color of each center, if there's a palette
+    fn get_center_colors(&self, objective: &Objective) -> HashMap<Position, Rgb> {
+        match &self.palette {
+            Some(palette) if !palette.is_empty() => sorted_centers(objective)
+                .iter()
+                .enumerate()
+                .map(|(i, gc)| (gc.position, palette[i % palette.len()]))
+                .collect(),
+            _ => HashMap::new(),
+        }
+    }
+}
+
+/// Parses a color written as `#rrggbb`
+pub fn parse_hex(color: &str) -> Option<Rgb> {
+    let hex = color.strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
+
+/// Renders the puzzle as the player sees it: the grid, the given walls and the centers
+pub fn render_puzzle(
+    width: usize,
+    height: usize,
+    objective: &Objective,
+    options: &PngOptions,
+) -> Vec<u8> {
+    let layout = options.get_layout(width, height);
+    let given = Board::with_walls(width, height, objective.walls.iter().copied());
+    let mut canvas = Canvas::new(&layout, options.background);
+    draw_walls(&mut canvas, &layout, &given, options);
+    draw_centers(
+        &mut canvas,
+        &layout,
+        objective,
+        &options.get_center_colors(objective),
+        options,
+    );
+    canvas.encode()
+}
+
+/// Renders the solution: the walls of every galaxy, and the galaxies filled
+/// with the color of their center if there's a palette
+pub fn render_solution(
+    objective: &Objective,
+    solution: &Universe,
+    options: &PngOptions,
+) -> Vec<u8> {
+    let width = solution.get_width();
+    let height = solution.get_height();
+    let layout = options.get_layout(width, height);
+    let walls = Board::with_walls(width, height, solution.get_borders());
+    let center_colors = options.get_center_colors(objective);
+    let mut canvas = Canvas::new(&layout, options.background);
+    for galaxy in solution.get_galaxies() {
+        if let Some(&color) = center_colors.get(&galaxy.center()) {
+            for cell in galaxy.get_positions() {
+                let x = layout.line_x(cell.column as usize);
+                let y = layout.line_y(cell.row as usize);
+                let pitch = layout.pitch();
+                canvas.fill_rect((x, y), (x + pitch, y + pitch), color, FILL_OPACITY);
+            }
+        }
+    }
+    draw_walls(&mut canvas, &layout, &walls, options);
+    draw_centers(&mut canvas, &layout, objective, &center_colors, options);
+    canvas.encode()
+}
+
+/// Draws thin grid lines where the board has no walls, thick lines where it does, and the frame
+fn draw_walls(canvas: &mut Canvas, layout: &Layout, board: &Board, options: &PngOptions) {
+    let (grid, walls) = partition_borders(board);
+    for border in &grid {
+        let (from, to) = layout.border_line(border);
+        canvas.draw_line(from, to, layout.wall_size / 2.0, options.grid);
+    }
+    for border in &walls {
+        let (from, to) = layout.border_line(border);
+        canvas.draw_line(from, to, layout.wall_size, options.wall);
+    }
+    let (right, bottom) = (layout.image_width(), layout.image_height());
+    let wall = layout.wall_size;
+    canvas.fill_rect((0.0, 0.0), (right, wall), options.wall, 1.0);
+    canvas.fill_rect((0.0, bottom - wall), (right, bottom), options.wall, 1.0);
+    canvas.fill_rect((0.0, 0.0), (wall, bottom), options.wall, 1.0);
+    canvas.fill_rect((right - wall, 0.0), (right, bottom), options.wall, 1.0);
+}
+
+fn draw_centers(
+    canvas: &mut Canvas,
+    layout: &Layout,
+    objective: &Objective,
+    center_colors: &HashMap<Position, Rgb>,
+    options: &PngOptions,
+) {
+    let radius = layout.center_radius();
+    for center in sorted_centers(objective) {
+        let point = layout.center_point(&center.position);
+        let color = center_colors
+            .get(&center.position)
+            .copied()
+            .unwrap_or(options.center);
+        canvas.fill_circle(point, radius + layout.wall_size / 4.0, options.background);
+        canvas.fill_circle(point, radius - layout.wall_size / 4.0, color);
+        if let Some(size) = center.size {
+            canvas.draw_number(point, radius * 1.2, radius * 1.5, size, options.center_text);
+        }
+    }
+}
+
+/// An rgb image that shapes are painted onto, with antialiased edges
+struct Canvas {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Canvas {
+    fn new(layout: &Layout, background: Rgb) -> Self {
+        let width = layout.image_width().ceil() as usize;
+        let height = layout.image_height().ceil() as usize;
+        Canvas {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    /// Mixes the color into the pixel, in proportion to how much of the pixel is covered
+    fn blend(&mut self, x: usize, y: usize, color: Rgb, coverage: f64) {
+        if x >= self.width || y >= self.height || coverage <= 0.0 {
+            return;
+        }
+        let coverage = coverage.min(1.0);
+        let pixel = &mut self.pixels[y * self.width + x];
+        for (channel, &target) in pixel.iter_mut().zip(color.iter()) {
+            let mixed = *channel as f64 * (1.0 - coverage) + target as f64 * coverage;
+            *channel = mixed.round() as u8;
+        }
+    }
+
+    /// Returns the range of pixels touched by the span from `from` to `to`
+    fn pixel_range(from: f64, to: f64, size: usize) -> std::ops::Range<usize> {
+        let start = from.floor().max(0.0) as usize;
+        let end = (to.ceil().max(0.0) as usize).min(size);
+        start..end
+    }
+
+    fn fill_rect(&mut self, (x1, y1): (f64, f64), (x2, y2): (f64, f64), color: Rgb, opacity: f64) {
+        let overlap = |from: f64, to: f64, pixel: usize| {
+            (to.min(pixel as f64 + 1.0) - from.max(pixel as f64)).max(0.0)
+        };
+        for y in Self::pixel_range(y1, y2, self.height) {
+            let vertical = overlap(y1, y2, y);
+            for x in Self::pixel_range(x1, x2, self.width) {
+                self.blend(x, y, color, opacity * vertical * overlap(x1, x2, x));
+            }
+        }
+    }
+
+    /// Draws a horizontal or vertical line with square caps, like the svg lines
+    fn draw_line(&mut self, (x1, y1): (f64, f64), (x2, y2): (f64, f64), width: f64, color: Rgb) {
+        let half = width / 2.0;
+        self.fill_rect(
+            (x1.min(x2) - half, y1.min(y2) - half),
+            (x1.max(x2) + half, y1.max(y2) + half),
+            color,
+            1.0,
+        );
+    }
+
+    fn fill_circle(&mut self, (cx, cy): (f64, f64), radius: f64, color: Rgb) {
+        const SAMPLES: usize = 4;
+        let ys = Self::pixel_range(cy - radius, cy + radius, self.height);
+        for y in ys {
+            for x in Self::pixel_range(cx - radius, cx + radius, self.width) {
+                let mut inside = 0;
+                for sy in 0..SAMPLES {
+                    for sx in 0..SAMPLES {
+                        let px = x as f64 + (sx as f64 + 0.5) / SAMPLES as f64;
+                        let py = y as f64 + (sy as f64 + 0.5) / SAMPLES as f64;
+                        if (px - cx).powi(2) + (py - cy).powi(2) <= radius * radius {
+                            inside += 1;
+                        }
+                    }
+                }
+                self.blend(x, y, color, inside as f64 / (SAMPLES * SAMPLES) as f64);
+            }
+        }
+    }
+
+    /// Draws the number centered on the point, with digits of the given height,
+    /// or smaller if needed to fit in the given width
+    fn draw_number(
+        &mut self,
+        (cx, cy): (f64, f64),
+        height: f64,
+        max_width: f64,
+        number: usize,
+        color: Rgb,
+    ) {
+        let digits: Vec<usize> = number
+            .to_string()
+            .bytes()
+            .map(|b| (b - b'0') as usize)
+            .collect();
+        // Each digit is 3 dots wide, with a dot of space between digits
+        let columns = (4 * digits.len() - 1) as f64;
+        let dot = (height / 5.0).min(max_width / columns);
+        let (left, top) = (cx - dot * columns / 2.0, cy - dot * 5.0 / 2.0);
+        for (i, &digit) in digits.iter().enumerate() {
+            for (row, bits) in DIGITS[digit].iter().enumerate() {
+                for column in 0..3 {
+                    if bits & (0b100 >> column) != 0 {
+                        let x = left + dot * (4 * i + column) as f64;
+                        let y = top + dot * row as f64;
+                        self.fill_rect((x, y), (x + dot, y + dot), color, 1.0);
+                    }
+                }
+            }
+        }
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let mut encoder = png::Encoder::new(&mut bytes, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder
+            .write_header()
+            .expect("writing to a vec cannot fail");
+        writer
+            .write_image_data(self.pixels.as_flattened())
+            .expect("the image data always matches the header");
+        writer.finish().expect("writing to a vec cannot fail");
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::galaxy::Galaxy;
+    use crate::model::objective::Objective;
+    use crate::model::position::Position;
+    use crate::model::universe::Universe;
+    use crate::render::layout::Layout;
+    use crate::render::png::{parse_hex, render_puzzle, render_solution, PngOptions, Rgb};
+
+    fn small_universe() -> Universe {
+        let galaxies = [
+            Galaxy::from([(0, 0), (0, 1)]),
+            Galaxy::from([(1, 0)]),
+            Galaxy::from([(1, 1)]),
+        ];
+        Universe::from(galaxies.as_slice())
+    }
+
+    /// Decodes the png, returning its size and pixels
+    fn decode(bytes: &[u8]) -> (usize, usize, Vec<Rgb>) {
+        let decoder = png::Decoder::new(std::io::Cursor::new(bytes));
+        let mut reader = decoder.read_info().unwrap();
+        let mut data = vec![0; reader.output_buffer_size().unwrap()];
+        let info = reader.next_frame(&mut data).unwrap();
+        assert_eq!(info.color_type, png::ColorType::Rgb);
+        let pixels = data[..info.buffer_size()]
+            .chunks(3)
+            .map(|c| [c[0], c[1], c[2]])
+            .collect();
+        (info.width as usize, info.height as usize, pixels)
+    }
+
+    fn pixel_at(image: &(usize, usize, Vec<Rgb>), (x, y): (f64, f64)) -> Rgb {
+        image.2[y as usize * image.0 + x as usize]
+    }
+
+    #[test]
+    fn tiny_cells_should_still_make_an_image() {
+        let objective = Objective::generate(&small_universe());
+        for cell_size in [0.0, -5.0, f64::NAN] {
+            let options = PngOptions {
+                cell_size,
+                ..PngOptions::default()
+            };
+            let (width, height, _) = decode(&render_puzzle(2, 2, &objective, &options));
+            assert!(width > 0 && height > 0);
+        }
+    }
+
+    #[test]
+    fn should_parse_hex_colors() {
+        assert_eq!(parse_hex("#ff8000"), Some([255, 128, 0]));
+        assert_eq!(parse_hex("ff8000"), None);
+        assert_eq!(parse_hex("#ff80"), None);
+    }
+
+    #[test]
+    fn puzzle_should_have_frame_and_centers() {
+        let universe = small_universe();
+        let objective = Objective::generate(&universe);
+        let options = PngOptions {
+            cell_size: 40.0,
+            center: [255, 0, 0],
+            ..PngOptions::default()
+        };
+        let layout = Layout::new(2, 2, options.cell_size);
+
+        let image = decode(&render_puzzle(2, 2, &objective, &options));
+
+        assert_eq!((image.0, image.1), (92, 92));
+        assert_eq!(pixel_at(&image, (1.0, 46.0)), [0, 0, 0]);
+        assert_eq!(pixel_at(&image, (20.0, 20.0)), [255, 255, 255]);
+        let center = layout.center_point(&Position::new(0, 1));
+        assert_eq!(pixel_at(&image, center), [255, 0, 0]);
+        // There's no wall between the two cells of the top galaxy
+        assert_eq!(pixel_at(&image, (46.0, 10.0)), [200, 200, 200]);
+    }
+
+    #[test]
+    fn solution_should_have_walls_and_fills() {
+        let universe = small_universe();
+        let objective = Objective::generate(&universe);
+        let options = PngOptions {
+            cell_size: 40.0,
+            palette: Some(vec![[0, 0, 255]]),
+            ..PngOptions::default()
+        };
+
+        let image = decode(&render_solution(&objective, &universe, &options));
+
+        // The wall between the two bottom galaxies
+        assert_eq!(pixel_at(&image, (46.0, 80.0)), [0, 0, 0]);
+        // Filled with a translucent blue
+        assert_eq!(pixel_at(&image, (10.0, 80.0)), [166, 166, 255]);
+    }
+
+    #[test]
+    fn sizes_should_be_drawn_in_the_center() {
+        let universe = small_universe();
+        let mut objective = Objective::generate(&universe);
+        objective.centers = objective
+            .centers
+            .into_iter()
+            .map(|mut gc| {
+                gc.size = Some(8);
+                gc
+            })
+            .collect();
+        let options = PngOptions {
+            cell_size: 40.0,
+            ..PngOptions::default()
+        };
+        let layout = Layout::new(2, 2, options.cell_size);
+
+        let image = decode(&render_puzzle(2, 2, &objective, &options));
+
+        // The middle of an 8 is lit
+        let (x, y) = layout.center_point(&Position::new(2, 0));
+        assert_eq!(pixel_at(&image, (x, y)), [255, 255, 255]);
+    }
+}
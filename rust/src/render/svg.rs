@@ -0,0 +1,358 @@
+use crate::model::board::Board;
+use crate::model::objective::Objective;
+use crate::model::position::Position;
+use crate::model::universe::Universe;
+use crate::render::layout::{partition_borders, sorted_centers, Layout};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Colors for galaxies, chosen to stay readable when printed in black and white
+pub const DEFAULT_PALETTE: &[&str] = &[
+    "#e6194b", "#3cb44b", "#4363d8", "#f58231", "#911eb4", "#42d4f4", "#f032e6", "#9a6324",
+    "#469990", "#808000", "#000075", "#800000",
+];
+
+const BACKGROUND_COLOR: &str = "white";
+const GRID_COLOR: &str = "#c8c8c8";
+const WALL_COLOR: &str = "black";
+const CENTER_COLOR: &str = "black";
+const CENTER_TEXT_COLOR: &str = "white";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgOptions {
+    /// The size of a cell in svg user units
+    pub cell_size: f64,
+    /// Colors assigned to the centers in order. With a palette, centers are drawn
+    /// in their color and galaxies in the solution are filled with it.
+    pub palette: Option<Vec<String>>,
+    /// Where to draw the image when it's nested in another svg. Without a frame,
+    /// the image is a standalone document sized after the board.
+    pub frame: Option<Frame>,
+}
+
+/// A rectangle in the user units of an enclosing svg
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Frame {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        SvgOptions {
+            cell_size: 40.0,
+            palette: None,
+            frame: None,
+        }
+    }
+}
+
+impl SvgOptions {
+    pub fn with_default_palette(self) -> Self {
+        SvgOptions {
+            palette: Some(DEFAULT_PALETTE.iter().map(|c| c.to_string()).collect()),
+            ..self
+        }
+    }
+
+    /// Returns the color of each center, if there's a palette
+    fn get_center_colors(&self, objective: &Objective) -> HashMap<Position, String> {
+        match &self.palette {
+            Some(palette) if !palette.is_empty() => sorted_centers(objective)
+                .iter()
+                .enumerate()
+                .map(|(i, gc)| (gc.position, palette[i % palette.len()].clone()))
+                .collect(),
+            _ => HashMap::new(),
+        }
+    }
+}
+
+/// Renders the puzzle as the player sees it: the grid, the given walls and the centers
+pub fn render_puzzle(
+    width: usize,
+    height: usize,
+    objective: &Objective,
+    options: &SvgOptions,
+) -> String {
+    let layout = Layout::new(width, height, options.cell_size);
+    let given = Board::with_walls(width, height, objective.walls.iter().copied());
+    let mut svg = String::new();
+    write_header(&mut svg, &layout, options.frame.as_ref());
+    write_walls(&mut svg, &layout, &given);
+    write_centers(
+        &mut svg,
+        &layout,
+        objective,
+        &options.get_center_colors(objective),
+    );
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Renders the solution: the walls of every galaxy, and the galaxies filled
+/// with the color of their center if there's a palette
+pub fn render_solution(objective: &Objective, solution: &Universe, options: &SvgOptions) -> String {
+    let width = solution.get_width();
+    let height = solution.get_height();
+    let layout = Layout::new(width, height, options.cell_size);
+    let walls = Board::with_walls(width, height, solution.get_borders());
+    let center_colors = options.get_center_colors(objective);
+    let mut svg = String::new();
+    write_header(&mut svg, &layout, options.frame.as_ref());
+    if !center_colors.is_empty() {
+        for galaxy in solution.get_galaxies() {
+            if let Some(color) = center_colors.get(&galaxy.center()) {
+                let mut cells: Vec<&Position> = galaxy.get_positions().collect();
+                cells.sort();
+                for cell in cells {
+                    write_cell(&mut svg, &layout, cell, color);
+                }
+            }
+        }
+    }
+    write_walls(&mut svg, &layout, &walls);
+    write_centers(&mut svg, &layout, objective, &center_colors);
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Formats a coordinate with at most two decimals
+pub(crate) fn number(value: f64) -> String {
+    let formatted = format!("{value:.2}");
+    formatted
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+fn write_header(svg: &mut String, layout: &Layout, frame: Option<&Frame>) {
+    let width = number(layout.image_width());
+    let height = number(layout.image_height());
+    match frame {
+        Some(frame) => writeln!(
+            svg,
+            r#"<svg x="{}" y="{}" width="{}" height="{}" viewBox="0 0 {width} {height}">"#,
+            number(frame.x),
+            number(frame.y),
+            number(frame.width),
+            number(frame.height),
+        ),
+        None => writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {width} {height}" width="{width}" height="{height}">"#
+        ),
+    }
+    .unwrap();
+    writeln!(
+        svg,
+        r#"<rect width="{width}" height="{height}" fill="{BACKGROUND_COLOR}"/>"#
+    )
+    .unwrap();
+}
+
+fn write_cell(svg: &mut String, layout: &Layout, cell: &Position, color: &str) {
+    writeln!(
+        svg,
+        r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{color}" fill-opacity="0.35"/>"#,
+        number(layout.line_x(cell.column as usize)),
+        number(layout.line_y(cell.row as usize)),
+        number(layout.pitch()),
+        number(layout.pitch()),
+    )
+    .unwrap();
+}
+
+fn write_line(
+    svg: &mut String,
+    (x1, y1): (f64, f64),
+    (x2, y2): (f64, f64),
+    stroke: &str,
+    stroke_width: f64,
+) {
+    writeln!(
+        svg,
+        r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{stroke}" stroke-width="{}" stroke-linecap="square"/>"#,
+        number(x1),
+        number(y1),
+        number(x2),
+        number(y2),
+        number(stroke_width),
+    )
+    .unwrap();
+}
+
+/// Writes thin grid lines where the board has no walls, thick lines where it does, and the frame
+fn write_walls(svg: &mut String, layout: &Layout, board: &Board) {
+    let (grid, walls) = partition_borders(board);
+
+    svg.push_str("<g>\n");
+    for border in &grid {
+        let (from, to) = layout.border_line(border);
+        write_line(svg, from, to, GRID_COLOR, layout.wall_size / 2.0);
+    }
+    for border in &walls {
+        let (from, to) = layout.border_line(border);
+        write_line(svg, from, to, WALL_COLOR, layout.wall_size);
+    }
+    writeln!(
+        svg,
+        r#"<rect x="{}" y="{}" width="{}" height="{}" fill="none" stroke="{WALL_COLOR}" stroke-width="{}"/>"#,
+        number(layout.wall_size / 2.0),
+        number(layout.wall_size / 2.0),
+        number(layout.image_width() - layout.wall_size),
+        number(layout.image_height() - layout.wall_size),
+        number(layout.wall_size),
+    )
+    .unwrap();
+    svg.push_str("</g>\n");
+}
+
+fn write_centers(
+    svg: &mut String,
+    layout: &Layout,
+    objective: &Objective,
+    center_colors: &HashMap<Position, String>,
+) {
+    svg.push_str("<g>\n");
+    for center in sorted_centers(objective) {
+        let (cx, cy) = layout.center_point(&center.position);
+        let color = center_colors
+            .get(&center.position)
+            .map(String::as_str)
+            .unwrap_or(CENTER_COLOR);
+        writeln!(
+            svg,
+            r#"<circle cx="{}" cy="{}" r="{}" fill="{color}" stroke="{BACKGROUND_COLOR}" stroke-width="{}"/>"#,
+            number(cx),
+            number(cy),
+            number(layout.center_radius()),
+            number(layout.wall_size / 2.0),
+        )
+        .unwrap();
+        if let Some(size) = center.size {
+            writeln!(
+                svg,
+                r#"<text x="{}" y="{}" font-family="sans-serif" font-weight="bold" font-size="{}" fill="{CENTER_TEXT_COLOR}" text-anchor="middle" dominant-baseline="central">{size}</text>"#,
+                number(cx),
+                number(cy),
+                number(layout.center_radius() * 1.2),
+            )
+            .unwrap();
+        }
+    }
+    svg.push_str("</g>\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::border::Border;
+    use crate::model::galaxy::Galaxy;
+    use crate::model::objective::Objective;
+    use crate::model::position::Position;
+    use crate::model::universe::Universe;
+    use crate::render::svg::{number, render_puzzle, render_solution, Frame, SvgOptions};
+
+    fn small_universe() -> Universe {
+        let galaxies = [
+            Galaxy::from([(0, 0), (0, 1)]),
+            Galaxy::from([(1, 0)]),
+            Galaxy::from([(1, 1)]),
+        ];
+        Universe::from(galaxies.as_slice())
+    }
+
+    fn count(svg: &str, pattern: &str) -> usize {
+        svg.matches(pattern).count()
+    }
+
+    #[test]
+    fn should_format_numbers_compactly() {
+        assert_eq!(number(4.0), "4");
+        assert_eq!(number(4.5), "4.5");
+        assert_eq!(number(1.0 / 3.0), "0.33");
+    }
+
+    #[test]
+    fn puzzle_should_have_grid_given_walls_and_centers() {
+        let universe = small_universe();
+        let mut objective = Objective::generate(&universe);
+        objective
+            .walls
+            .insert(Border::new(Position::new(1, 0), Position::new(1, 1)));
+        objective.centers = objective
+            .centers
+            .into_iter()
+            .map(|mut gc| {
+                if gc.position == Position::new(0, 1) {
+                    gc.size = Some(2);
+                }
+                gc
+            })
+            .collect();
+
+        let svg = render_puzzle(2, 2, &objective, &SvgOptions::default());
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 92 92\""));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(count(&svg, "<line"), 4);
+        assert_eq!(count(&svg, "stroke=\"black\" stroke-width=\"4\""), 2);
+        assert_eq!(count(&svg, "<circle"), 3);
+        assert_eq!(count(&svg, "<text"), 1);
+        assert!(svg.contains(">2</text>"));
+    }
+
+    #[test]
+    fn solution_should_have_galaxy_walls_and_colors() {
+        let universe = small_universe();
+        let objective = Objective::generate(&universe);
+        let options = SvgOptions {
+            palette: Some(vec![
+                "red".to_string(),
+                "green".to_string(),
+                "blue".to_string(),
+            ]),
+            ..SvgOptions::default()
+        };
+
+        let svg = render_solution(&objective, &universe, &options);
+
+        assert_eq!(count(&svg, "stroke=\"black\" stroke-width=\"4\""), 4);
+        assert_eq!(count(&svg, "fill-opacity"), 4);
+        assert_eq!(count(&svg, "fill=\"red\" fill-opacity"), 2);
+        assert_eq!(
+            count(&svg, "<circle cx=\"46\" cy=\"24\" r=\"12\" fill=\"red\""),
+            1
+        );
+        assert_eq!(count(&svg, "<circle"), 3);
+    }
+
+    #[test]
+    fn solution_without_palette_should_not_fill_galaxies() {
+        let universe = small_universe();
+        let objective = Objective::generate(&universe);
+        let svg = render_solution(&objective, &universe, &SvgOptions::default());
+        assert_eq!(count(&svg, "fill-opacity"), 0);
+    }
+
+    #[test]
+    fn framed_image_should_be_placed_in_its_frame() {
+        let universe = small_universe();
+        let objective = Objective::generate(&universe);
+        let options = SvgOptions {
+            frame: Some(Frame {
+                x: 10.0,
+                y: 20.5,
+                width: 50.0,
+                height: 50.0,
+            }),
+            ..SvgOptions::default()
+        };
+        let svg = render_puzzle(2, 2, &objective, &options);
+        assert!(svg.starts_with(
+            "<svg x=\"10\" y=\"20.5\" width=\"50\" height=\"50\" viewBox=\"0 0 92 92\">"
+        ));
+    }
+}
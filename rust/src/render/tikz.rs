@@ -0,0 +1,206 @@
+use crate::model::border::Border;
+use crate::model::objective::Objective;
+use crate::model::position::Position;
+use crate::model::universe::Universe;
+use crate::render::layout::sorted_centers;
+use crate::render::svg::number;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+const CENTER_RADIUS: f64 = 0.22;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TikzOptions {
+    /// The size of a cell in centimeters
+    pub cell_size: f64,
+    /// Whether to wrap the picture in a standalone document. Without it, only the
+    /// `tikzpicture` environment is written, to be included in another document.
+    pub standalone: bool,
+}
+
+impl Default for TikzOptions {
+    fn default() -> Self {
+        TikzOptions {
+            cell_size: 1.0,
+            standalone: true,
+        }
+    }
+}
+
+/// Writes the puzzle as the player sees it: the grid, the given walls and the centers
+pub fn render_puzzle(
+    width: usize,
+    height: usize,
+    objective: &Objective,
+    options: &TikzOptions,
+) -> String {
+    let walls: BTreeSet<Border> = objective.walls.iter().copied().collect();
+    render(width, height, objective, &walls, options)
+}
+
+/// Writes the solution: the borders of every galaxy, and the centers
+pub fn render_solution(
+    objective: &Objective,
+    solution: &Universe,
+    options: &TikzOptions,
+) -> String {
+    let walls: BTreeSet<Border> = solution
+        .get_galaxies()
+        .iter()
+        .flat_map(|galaxy| galaxy.get_borders())
+        .collect();
+    render(
+        solution.get_width(),
+        solution.get_height(),
+        objective,
+        &walls,
+        options,
+    )
+}
+
+fn render(
+    width: usize,
+    height: usize,
+    objective: &Objective,
+    walls: &BTreeSet<Border>,
+    options: &TikzOptions,
+) -> String {
+    let mut tex = String::new();
+    if options.standalone {
+        tex.push_str("\\documentclass[tikz,border=2mm]{standalone}\n");
+        tex.push_str("\\begin{document}\n");
+    }
+    // Rows grow downwards, like on the board
+    let cell_size = number(options.cell_size);
+    writeln!(
+        tex,
+        "\\begin{{tikzpicture}}[x={cell_size}cm, y=-{cell_size}cm, line cap=rect]"
+    )
+    .unwrap();
+    writeln!(tex, "\\draw[gray!40, thin] (0,0) grid ({width},{height});").unwrap();
+    for wall in walls {
+        let ((x1, y1), (x2, y2)) = get_border_line(wall);
+        writeln!(tex, "\\draw[ultra thick] ({x1},{y1}) -- ({x2},{y2});").unwrap();
+    }
+    writeln!(
+        tex,
+        "\\draw[ultra thick] (0,0) rectangle ({width},{height});"
+    )
+    .unwrap();
+    for center in sorted_centers(objective) {
+        let (x, y) = get_center_point(&center.position);
+        writeln!(
+            tex,
+            "\\fill[draw=white, thick] ({x},{y}) circle ({});",
+            number(CENTER_RADIUS)
+        )
+        .unwrap();
+        if let Some(size) = center.size {
+            writeln!(
+                tex,
+                "\\node[text=white, font=\\sffamily\\bfseries\\footnotesize] at ({x},{y}) {{{size}}};"
+            )
+            .unwrap();
+        }
+    }
+    tex.push_str("\\end{tikzpicture}\n");
+    if options.standalone {
+        tex.push_str("\\end{document}\n");
+    }
+    tex
+}
+
+/// Returns the end points of the border in cell units, where the top left corner of the board is (0, 0)
+fn get_border_line(border: &Border) -> ((i32, i32), (i32, i32)) {
+    let p2 = border.p2();
+    if border.is_vertical() {
+        ((p2.column, p2.row), (p2.column, p2.row + 1))
+    } else {
+        ((p2.column, p2.row), (p2.column + 1, p2.row))
+    }
+}
+
+/// Returns the point of a center given in half-steps, in cell units
+fn get_center_point(center: &Position) -> (String, String) {
+    let x = (center.column as f64 + 1.0) / 2.0;
+    let y = (center.row as f64 + 1.0) / 2.0;
+    (number(x), number(y))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::border::Border;
+    use crate::model::galaxy::Galaxy;
+    use crate::model::objective::Objective;
+    use crate::model::position::Position;
+    use crate::model::universe::Universe;
+    use crate::render::tikz::{render_puzzle, render_solution, TikzOptions};
+    use indoc::indoc;
+
+    fn small_universe() -> Universe {
+        let galaxies = [
+            Galaxy::from([(0, 0), (0, 1)]),
+            Galaxy::from([(1, 0)]),
+            Galaxy::from([(1, 1)]),
+        ];
+        Universe::from(galaxies.as_slice())
+    }
+
+    #[test]
+    fn puzzle_should_be_a_standalone_document() {
+        let universe = small_universe();
+        let mut objective = Objective::generate(&universe);
+        objective
+            .walls
+            .insert(Border::new(Position::new(1, 0), Position::new(1, 1)));
+        objective.centers = objective
+            .centers
+            .into_iter()
+            .map(|mut gc| {
+                if gc.position == Position::new(0, 1) {
+                    gc.size = Some(2);
+                }
+                gc
+            })
+            .collect();
+
+        let tex = render_puzzle(2, 2, &objective, &TikzOptions::default());
+
+        assert_eq!(
+            tex,
+            indoc! {r"
+                \documentclass[tikz,border=2mm]{standalone}
+                \begin{document}
+                \begin{tikzpicture}[x=1cm, y=-1cm, line cap=rect]
+                \draw[gray!40, thin] (0,0) grid (2,2);
+                \draw[ultra thick] (1,1) -- (1,2);
+                \draw[ultra thick] (0,0) rectangle (2,2);
+                \fill[draw=white, thick] (1,0.5) circle (0.22);
+                \node[text=white, font=\sffamily\bfseries\footnotesize] at (1,0.5) {2};
+                \fill[draw=white, thick] (0.5,1.5) circle (0.22);
+                \fill[draw=white, thick] (1.5,1.5) circle (0.22);
+                \end{tikzpicture}
+                \end{document}
+            "}
+        );
+    }
+
+    #[test]
+    fn solution_should_draw_galaxy_borders() {
+        let universe = small_universe();
+        let objective = Objective::generate(&universe);
+        let options = TikzOptions {
+            cell_size: 0.5,
+            standalone: false,
+        };
+
+        let tex = render_solution(&objective, &universe, &options);
+
+        assert!(tex.starts_with("\\begin{tikzpicture}[x=0.5cm, y=-0.5cm, line cap=rect]\n"));
+        assert!(tex.ends_with("\\end{tikzpicture}\n"));
+        // 8 edges on the frame and 3 between the galaxies
+        assert_eq!(tex.matches(" -- ").count(), 11);
+        assert!(tex.contains("\\draw[ultra thick] (0,1) -- (1,1);"));
+        assert!(tex.contains("\\draw[ultra thick] (1,1) -- (1,2);"));
+    }
+}
@@ -2,15 +2,21 @@ import { useEffect, useReducer } from "react";
 import styles from "./App.module.css";
 import boardStyles from "./Board.module.css";
 
-import { GameState, generate_state } from "../rust/pkg";
+import { Direction, GameState, Strictness, generate_state } from "../rust/pkg";
 import type { StateView } from "../rust/bindings/StateView.ts";
 import type { Border } from "../rust/bindings/Border.ts";
 import type { Position } from "../rust/bindings/Position.ts";
+import type { ProgressReport } from "../rust/bindings/ProgressReport.ts";
+import type { Solvability } from "../rust/bindings/Solvability.ts";
 import { clsx } from "clsx";
 
 type AppState = {
   gameState: GameState;
   view: StateView;
+  /// The result of the last progress check, until the next action
+  progress?: ProgressReport;
+  /// The result of the last solvability check, until the next action
+  solvability?: Solvability;
 };
 
 type ToggleAction = {
@@ -25,15 +31,53 @@ type Action =
   | { type: "UNDO" }
   | { type: "REDO" }
   | { type: "HINT" }
-  | { type: "CLEAR" };
+  | { type: "CLEAR" }
+  | { type: "CHECK_PROGRESS" }
+  | { type: "CHECK_SOLVABLE" }
+  | { type: "GIVE_UP" }
+  | { type: "MOVE_CURSOR"; direction: Direction; jump: boolean }
+  | { type: "TOGGLE_TOWARDS"; direction: Direction }
+  | { type: "CURSOR_TO_CENTER"; forward: boolean };
 
-function makeInitialState(): AppState {
+const ARROW_DIRECTIONS: Record<string, Direction> = {
+  ArrowUp: Direction.Up,
+  ArrowRight: Direction.Right,
+  ArrowDown: Direction.Down,
+  ArrowLeft: Direction.Left,
+};
+
+const SAVED_GAME_KEY = "laniakea.savedGame";
+
+function loadSavedGame(): GameState | undefined {
+  const savedGame = localStorage.getItem(SAVED_GAME_KEY);
+  if (savedGame === null) {
+    return undefined;
+  }
+  try {
+    return GameState.import(savedGame);
+  } catch (error) {
+    console.warn("Discarding saved game", error);
+    localStorage.removeItem(SAVED_GAME_KEY);
+    return undefined;
+  }
+}
+
+// Starts a new game where walls through centers are refused
+function newGame(): GameState {
   const gameState = generate_state();
+  gameState.set_strictness(Strictness.Strict);
+  return gameState;
+}
+
+function makeInitialState(
+  gameState: GameState = loadSavedGame() ?? newGame(),
+): AppState {
   const view = gameState.get_view() as StateView;
   return { gameState, view };
 }
 
 function reducer(state: AppState, action: Action): AppState {
+  state.gameState.tick_timer(Date.now());
   switch (action.type) {
     case "TOGGLE": {
       const { border } = action;
@@ -61,31 +105,107 @@ function reducer(state: AppState, action: Action): AppState {
       state.gameState.take_hint();
       break;
     }
+    case "CLEAR": {
+      state.gameState.clear_board();
+      break;
+    }
+    case "CHECK_PROGRESS": {
+      const progress = state.gameState.check_progress(true) as ProgressReport;
+      localStorage.setItem(SAVED_GAME_KEY, state.gameState.export());
+      return {
+        ...state,
+        view: state.gameState.get_view() as StateView,
+        progress,
+      };
+    }
+    case "MOVE_CURSOR": {
+      if (action.jump) {
+        state.gameState.jump_cursor(action.direction);
+      } else {
+        state.gameState.move_cursor(action.direction);
+      }
+      break;
+    }
+    case "TOGGLE_TOWARDS": {
+      state.gameState.toggle_edge_towards(action.direction);
+      break;
+    }
+    case "CURSOR_TO_CENTER": {
+      state.gameState.cursor_to_center(action.forward);
+      break;
+    }
+    case "GIVE_UP": {
+      state.gameState.reveal_solution();
+      break;
+    }
+    case "CHECK_SOLVABLE": {
+      return {
+        ...state,
+        solvability: state.gameState.check_solvable() as Solvability,
+      };
+    }
     case "NEW_GAME": {
       state.gameState.free();
-      return makeInitialState();
+      const gameState = newGame();
+      gameState.resume_timer(Date.now());
+      localStorage.setItem(SAVED_GAME_KEY, gameState.export());
+      return makeInitialState(gameState);
     }
   }
 
+  localStorage.setItem(SAVED_GAME_KEY, state.gameState.export());
+
   return {
     ...state,
     view: state.gameState.get_view() as StateView,
+    progress: undefined,
+    solvability: undefined,
   };
 }
 
 function App() {
-  const [state, dispatch] = useReducer(reducer, null, makeInitialState);
+  const [state, dispatch] = useReducer(reducer, undefined, makeInitialState);
 
   useEffect(() => {
     return () => state.gameState.free();
   }, []);
 
   useEffect(() => {
-    const beforeUnload = (event: BeforeUnloadEvent) => {
+    const { gameState } = state;
+    const onVisibilityChange = () => {
+      if (document.hidden) {
+        gameState.pause_timer(Date.now());
+      } else {
+        gameState.resume_timer(Date.now());
+      }
+    };
+    onVisibilityChange();
+    document.addEventListener("visibilitychange", onVisibilityChange);
+    return () => {
+      document.removeEventListener("visibilitychange", onVisibilityChange);
+    };
+  }, [state.gameState]);
+
+  useEffect(() => {
+    // Arrows move the cursor, shift moves a whole cell, ctrl toggles the edge in that
+    // direction, and tab goes through the centers
+    const onKeyDown = (event: KeyboardEvent) => {
+      const direction = ARROW_DIRECTIONS[event.key];
+      if (direction !== undefined && event.ctrlKey) {
+        dispatch({ type: "TOGGLE_TOWARDS", direction });
+      } else if (direction !== undefined) {
+        dispatch({ type: "MOVE_CURSOR", direction, jump: event.shiftKey });
+      } else if (event.key === "Tab") {
+        dispatch({ type: "CURSOR_TO_CENTER", forward: !event.shiftKey });
+      } else {
+        return;
+      }
       event.preventDefault();
     };
-    window.addEventListener("beforeunload", beforeUnload);
-    return () => window.removeEventListener("beforeunload", beforeUnload);
+    document.addEventListener("keydown", onKeyDown);
+    return () => {
+      document.removeEventListener("keydown", onKeyDown);
+    };
   }, []);
 
   useEffect(() => {
@@ -136,6 +256,38 @@ function App() {
               New game
             </button>
           )}
+          {!state.view.is_solved && (
+            <button
+              className={styles.btn}
+              onClick={() => dispatch({ type: "CLEAR" })}
+            >
+              Clear
+            </button>
+          )}
+          {!state.view.is_solved && (
+            <button
+              className={styles.btn}
+              onClick={() => dispatch({ type: "CHECK_PROGRESS" })}
+            >
+              Check Progress
+            </button>
+          )}
+          {!state.view.is_solved && (
+            <button
+              className={styles.btn}
+              onClick={() => dispatch({ type: "CHECK_SOLVABLE" })}
+            >
+              Still Solvable?
+            </button>
+          )}
+          {!state.view.is_solved && !state.view.stats.gave_up && (
+            <button
+              className={styles.btn}
+              onClick={() => dispatch({ type: "GIVE_UP" })}
+            >
+              Give Up
+            </button>
+          )}
           {!state.view.is_solved && (
             <button
               className={styles.btn}
@@ -144,6 +296,32 @@ function App() {
               Check Solution
             </button>
           )}
+          {state.progress && (
+            <p>
+              {state.progress.wrong_walls.length === 0
+                ? "No wrong walls so far"
+                : `${state.progress.wrong_walls.length} wrong walls`}
+              {`, ${state.progress.missing_walls} walls to go`}
+            </p>
+          )}
+          {state.view.solution_diff && (
+            <p>
+              {`${state.view.solution_diff.correct_walls.length} walls were right, `}
+              {`${state.view.solution_diff.walls_to_remove.length} should go and `}
+              {`${state.view.solution_diff.walls_to_add.length} were missing`}
+            </p>
+          )}
+          {state.solvability && (
+            <p>
+              {state.solvability === "Solvable"
+                ? "Still solvable"
+                : state.solvability === "Unknown"
+                  ? "Could not tell whether it is still solvable"
+                  : `No longer solvable, check these walls: ${state.solvability.Unsolvable.conflicting_walls
+                      .map(({ p1, p2 }) => `(${p1.row}, ${p1.column})–(${p2.row}, ${p2.column})`)
+                      .join(", ")}`}
+            </p>
+          )}
         </div>
       </div>
     </div>
@@ -190,6 +368,8 @@ function Board({ view, onToggle }: BoardProps) {
             const centerless = view.error?.centerless_cells.some(
               (p) => p.row === row && p.column === col,
             );
+            const complete =
+              view.regions.regions[view.regions.ids[row][col]].complete;
 
             return (
               <rect
@@ -200,6 +380,7 @@ function Board({ view, onToggle }: BoardProps) {
                 height={CELL_SIZE + 2.0 * WALL_SIZE}
                 className={clsx(
                   boardStyles.cell,
+                  complete && boardStyles.complete,
                   centerless && boardStyles.centerless,
                 )}
               />
@@ -375,6 +556,22 @@ function Board({ view, onToggle }: BoardProps) {
           );
         })
       }
+
+      {
+        // Render the keyboard cursor, in half-steps like the centers
+        <circle
+          cx={
+            WALL_SIZE / 2.0 +
+            ((WALL_SIZE + CELL_SIZE) / 2.0) * (view.cursor.column + 1)
+          }
+          cy={
+            WALL_SIZE / 2.0 +
+            ((WALL_SIZE + CELL_SIZE) / 2.0) * (view.cursor.row + 1)
+          }
+          r={CELL_SIZE / 4.0}
+          className={boardStyles.cursor}
+        />
+      }
     </svg>
   );
 }